@@ -0,0 +1,13 @@
+pragma circom 2.0.0;
+
+// Smallest circuit with a public output, a public input and private inputs, whose artifacts
+// `make fixtures` copies to crates/near_zk_prover/tests/data for the file format tests
+template Fixture() {
+    signal input a;
+    signal input b[2];
+    signal output c;
+
+    c <== a * b[0] + b[1];
+}
+
+component main {public [a]} = Fixture();
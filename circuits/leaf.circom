@@ -0,0 +1,56 @@
+pragma circom 2.0.0;
+
+include "../node_modules/circomlib/circuits/poseidon.circom";
+include "../node_modules/circomlib/circuits/comparators.circom";
+
+// Hardened leaf, matching `LeafScheme::Hardened` in the contract.
+// The answer is stretched with the salt kept by the user,
+//     key[0] = answer, key[i + 1] = Poseidon(key[i], salt),
+// and the leaf and nullifier are computed from key[rounds]:
+//     leaf = Poseidon(question, key[rounds]), nullifier = Poseidon(key[rounds]).
+// `rounds` is private and at most `maxRounds`, with `rounds = 0` this is the legacy leaf.
+template HardenedLeaf(maxRounds) {
+    signal input question;
+    signal input answer;
+    signal input salt;
+    signal input rounds;
+
+    signal output leaf;
+    signal output nullifier;
+
+    component stretch[maxRounds];
+    component isRound[maxRounds + 1];
+
+    signal keys[maxRounds + 1];
+    signal selected[maxRounds + 1];
+    keys[0] <== answer;
+
+    var key = 0;
+    var found = 0;
+    for (var i = 0; i <= maxRounds; i++) {
+        if (i > 0) {
+            stretch[i - 1] = Poseidon(2);
+            stretch[i - 1].inputs[0] <== keys[i - 1];
+            stretch[i - 1].inputs[1] <== salt;
+            keys[i] <== stretch[i - 1].out;
+        }
+
+        isRound[i] = IsEqual();
+        isRound[i].in[0] <== rounds;
+        isRound[i].in[1] <== i;
+        selected[i] <== isRound[i].out * keys[i];
+
+        key += selected[i];
+        found += isRound[i].out;
+    }
+    found === 1;
+
+    component leafHash = Poseidon(2);
+    leafHash.inputs[0] <== question;
+    leafHash.inputs[1] <== key;
+    leaf <== leafHash.out;
+
+    component nullifierHash = Poseidon(1);
+    nullifierHash.inputs[0] <== key;
+    nullifier <== nullifierHash.out;
+}
@@ -34,4 +34,4 @@ template Main(nLevels) {
     nullifier <== nullifierHash.out;
 }
 
-component main {public [root, new_owner]} = Main(2);
\ No newline at end of file
+component main {public [root, question, new_owner]} = Main(2);
\ No newline at end of file
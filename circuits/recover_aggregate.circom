@@ -0,0 +1,45 @@
+pragma circom 2.0.0;
+
+include "./tree.circom";
+include "./leaf.circom";
+
+// Answers to k questions under one root in one proof, each leaf as in `recover_hardened.circom`.
+// The leaves need not differ here, the contract rejects repeated nullifiers and questions.
+template Main(nLevels, k, maxRounds) {
+    signal input pathIndices[k][nLevels];
+    signal input siblings[k][nLevels];
+
+    signal input root;
+    signal input question[k];
+    signal input answer[k];
+    signal input salt[k];
+    signal input rounds[k];
+
+    signal input new_owner;
+
+    signal output nullifier[k];
+
+    component leaf[k];
+    component verifyRoot[k];
+
+    for (var i = 0; i < k; i++) {
+        leaf[i] = HardenedLeaf(maxRounds);
+        leaf[i].question <== question[i];
+        leaf[i].answer <== answer[i];
+        leaf[i].salt <== salt[i];
+        leaf[i].rounds <== rounds[i];
+
+        verifyRoot[i] = SecretProtectionTree(nLevels);
+        verifyRoot[i].leaf <== leaf[i].leaf;
+
+        for (var j = 0; j < nLevels; j++) {
+            verifyRoot[i].siblings[j] <== siblings[i][j];
+            verifyRoot[i].pathIndices[j] <== pathIndices[i][j];
+        }
+        root === verifyRoot[i].root;
+
+        nullifier[i] <== leaf[i].nullifier;
+    }
+}
+
+component main {public [root, question, new_owner]} = Main(2, 2, 16);
\ No newline at end of file
@@ -0,0 +1,38 @@
+pragma circom 2.0.0;
+
+include "./tree.circom";
+include "./leaf.circom";
+
+template Main(nLevels, maxRounds) {
+    signal input pathIndices[nLevels];
+    signal input siblings[nLevels];
+
+    signal input root;
+    signal input question;
+    signal input answer;
+    signal input salt;
+    signal input rounds;
+
+    signal input new_owner;
+
+    signal output nullifier;
+
+    component leaf = HardenedLeaf(maxRounds);
+    leaf.question <== question;
+    leaf.answer <== answer;
+    leaf.salt <== salt;
+    leaf.rounds <== rounds;
+
+    component verifyRoot = SecretProtectionTree(nLevels);
+    verifyRoot.leaf <== leaf.leaf;
+
+    for (var i = 0; i < nLevels; i++) {
+        verifyRoot.siblings[i] <== siblings[i];
+        verifyRoot.pathIndices[i] <== pathIndices[i];
+    }
+    root === verifyRoot.root;
+
+    nullifier <== leaf.nullifier;
+}
+
+component main {public [root, question, new_owner]} = Main(2, 16);
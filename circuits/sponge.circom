@@ -0,0 +1,43 @@
+pragma circom 2.0.0;
+
+include "../node_modules/circomlib/circuits/poseidon.circom";
+
+// Poseidon sponge matching `PoseidonSponge` in the contract.
+// The permutation is Poseidon with 2 rate elements and 1 capacity element, the
+// capacity starts at 2^64 + nOuts - 1. Inputs are added to the rate elements two
+// at a time and the last block is padded with zeros. Outputs are read from the
+// rate elements, permuting again for every further pair.
+//
+// To hash a byte string, pass the message split into 31 byte chunks read as big
+// endian integers, followed by the byte length, see `pack_bytes`.
+template PoseidonSponge(nInputs, nOuts) {
+    signal input inputs[nInputs];
+    signal output out[nOuts];
+
+    var nBlocks = nInputs == 0 ? 1 : (nInputs + 1) \ 2;
+    var nPerms = nBlocks + (nOuts - 1) \ 2;
+
+    component perms[nPerms];
+
+    for (var i = 0; i < nPerms; i++) {
+        perms[i] = PoseidonEx(2, 3);
+        if (i == 0) {
+            perms[i].initialState <== 18446744073709551616 + nOuts - 1;
+        } else {
+            perms[i].initialState <== perms[i - 1].out[0];
+        }
+
+        for (var j = 0; j < 2; j++) {
+            var state = i == 0 ? 0 : perms[i - 1].out[j + 1];
+            if (i < nBlocks && 2 * i + j < nInputs) {
+                perms[i].inputs[j] <== state + inputs[2 * i + j];
+            } else {
+                perms[i].inputs[j] <== state;
+            }
+        }
+    }
+
+    for (var k = 0; k < nOuts; k++) {
+        out[k] <== perms[nBlocks - 1 + k \ 2].out[1 + k % 2];
+    }
+}
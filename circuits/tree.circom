@@ -37,4 +37,48 @@ template SecretProtectionTree(nLevels) {
     }
 
     root <== hashes[nLevels];
-}
\ No newline at end of file
+}
+
+// Domain separated variant, matching `TreeMode::DomainSeparated`.
+// The leaf value is hashed with domain tag 1 and the node `l` layers above
+// the leaves with domain tag `l + 1`, passed as the Poseidon capacity element.
+template SecretProtectionTreeDS(nLevels) {
+    signal input leaf;
+    signal input pathIndices[nLevels];
+    signal input siblings[nLevels];
+
+    signal output root;
+
+    component leafHash = PoseidonEx(1, 1);
+    leafHash.initialState <== 1;
+    leafHash.inputs[0] <== leaf;
+
+    component poseidons[nLevels];
+    component mux[nLevels];
+
+    signal hashes[nLevels + 1];
+    hashes[0] <== leafHash.out[0];
+
+    for (var i = 0; i < nLevels; i++) {
+        pathIndices[i] * (1 - pathIndices[i]) === 0;
+
+        poseidons[i] = PoseidonEx(2, 1);
+        mux[i] = MultiMux1(2);
+
+        mux[i].c[0][0] <== hashes[i];
+        mux[i].c[0][1] <== siblings[i];
+
+        mux[i].c[1][0] <== siblings[i];
+        mux[i].c[1][1] <== hashes[i];
+
+        mux[i].s <== pathIndices[i];
+
+        poseidons[i].initialState <== i + 2;
+        poseidons[i].inputs[0] <== mux[i].out[0];
+        poseidons[i].inputs[1] <== mux[i].out[1];
+
+        hashes[i + 1] <== poseidons[i].out[0];
+    }
+
+    root <== hashes[nLevels];
+}
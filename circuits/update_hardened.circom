@@ -0,0 +1,46 @@
+pragma circom 2.0.0;
+
+include "./tree.circom";
+include "./leaf.circom";
+
+template Main(nLevels, maxRounds) {
+    signal input pathIndices[nLevels];
+    signal input siblings[nLevels];
+
+    signal input root;
+    signal input oldQuestion;
+    signal input oldAnswer;
+    signal input oldSalt;
+    signal input oldRounds;
+
+    signal input newQuestion;
+    signal input newAnswer;
+    signal input newSalt;
+    signal input newRounds;
+    signal output leaf;
+
+
+    component oldLeaf = HardenedLeaf(maxRounds);
+    oldLeaf.question <== oldQuestion;
+    oldLeaf.answer <== oldAnswer;
+    oldLeaf.salt <== oldSalt;
+    oldLeaf.rounds <== oldRounds;
+
+    component verifyOldRoot = SecretProtectionTree(nLevels);
+    verifyOldRoot.leaf <== oldLeaf.leaf;
+    for (var i = 0; i < nLevels; i++) {
+        verifyOldRoot.siblings[i] <== siblings[i];
+        verifyOldRoot.pathIndices[i] <== pathIndices[i];
+    }
+    root === verifyOldRoot.root;
+
+    component newLeaf = HardenedLeaf(maxRounds);
+    newLeaf.question <== newQuestion;
+    newLeaf.answer <== newAnswer;
+    newLeaf.salt <== newSalt;
+    newLeaf.rounds <== newRounds;
+
+    leaf <== newLeaf.leaf;
+}
+
+component main {public [root, oldQuestion, newQuestion]} = Main(2, 16);
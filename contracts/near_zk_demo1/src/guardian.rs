@@ -0,0 +1,166 @@
+use crate::*;
+
+/// Account that approves recoveries, each approval counting `weight` toward the guardian threshold
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Guardian {
+    pub account_id: AccountId,
+    pub weight: u32,
+}
+
+/// Guardian's approval to hand the account to `new_owner`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Approval {
+    pub guardian: AccountId,
+    pub new_owner: AccountId,
+}
+
+/// Factors a recovery needs, e.g. answers of weight 2 and 1 guardian.
+/// `answer_threshold` is the summed question weight the recover proofs must reach. `None` means every question,
+/// and it is capped at the total weight of the questions either way.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RecoveryPolicy {
+    pub answer_threshold: Option<u32>,
+    pub guardian_threshold: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Adds a guardian, or changes the weight of an existing one. `weight` defaults to 1.
+    pub fn set_guardian(&mut self, account_id: AccountId, weight: Option<u32>) -> Option<U64> {
+        let weight = weight.unwrap_or(1);
+        assert!(weight > 0, "Invalid guardian weight");
+        self.schedule(QueuedAction::SetGuardian { guardian: Guardian { account_id, weight } })
+    }
+
+    /// Removes a guardian together with its pending approval
+    pub fn remove_guardian(&mut self, account_id: AccountId) -> Option<U64> {
+        self.schedule(QueuedAction::RemoveGuardian { account_id })
+    }
+
+    pub fn set_recovery_policy(&mut self, answer_threshold: Option<u32>, guardian_threshold: u32) -> Option<U64> {
+        assert!(answer_threshold != Some(0) || guardian_threshold > 0, "Invalid recovery policy");
+        self.schedule(QueuedAction::SetRecoveryPolicy { policy: RecoveryPolicy { answer_threshold, guardian_threshold } })
+    }
+
+    /// Approval of a guardian to hand the account to `new_owner`, replacing its earlier approval.
+    /// `new_owner` must match the recovery in progress, if any. Guardians only start a recovery together,
+    /// once the approvals of one account reach the guardian threshold.
+    pub fn approve_recovery(&mut self, new_owner: AccountId) {
+        let caller = env::predecessor_account_id();
+        assert!(self.guardian_weight(&caller).is_some(), "Not guardian");
+        if let Some(current) = &self.new_owner {
+            assert!(current == &new_owner, "Invalid new_owner_account");
+        }
+        match self.approvals.iter_mut().find(|v| v.guardian == caller) {
+            Some(approval) => {
+                assert!(approval.new_owner != new_owner, "Repeat approval");
+                approval.new_owner = new_owner.clone();
+            }
+            None => self.approvals.push(Approval { guardian: caller, new_owner: new_owner.clone() }),
+        }
+
+        let threshold = self.recovery_policy.guardian_threshold;
+        if threshold > 0 && self.get_approved_weight(new_owner.clone()) >= threshold {
+            self.start_recovery(new_owner);
+        }
+        self.finish_recovery_if_complete();
+    }
+
+    /// Stops the recovery in progress, dropping its proofs and approvals.
+    /// Callable by a guardian or the recovering account, not by the owner whose key the recovery may replace.
+    pub fn cancel_recovery(&mut self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            self.guardian_weight(&caller).is_some() || self.new_owner.as_ref() == Some(&caller),
+            "Not guardian or recovering account"
+        );
+        self.reset_recovery();
+    }
+
+    pub fn get_guardians(&self) -> Vec<Guardian> {
+        self.guardians.clone()
+    }
+
+    pub fn get_recovery_policy(&self) -> RecoveryPolicy {
+        self.recovery_policy
+    }
+
+    pub fn get_recovery_approvals(&self) -> Vec<Approval> {
+        self.approvals.clone()
+    }
+
+    /// Summed current weight of the guardians approving `new_owner`, complete once it reaches the guardian threshold
+    pub fn get_approved_weight(&self, new_owner: AccountId) -> u32 {
+        self.approvals.iter().filter(|v| v.new_owner == new_owner).filter_map(|v| self.guardian_weight(&v.guardian)).sum()
+    }
+
+    pub fn get_new_owner(&self) -> Option<AccountId> {
+        self.new_owner.clone()
+    }
+}
+
+impl Contract {
+    /// Question weight the recover proofs of a recovery must reach under the current policy
+    pub fn required_answer_weight(&self) -> u32 {
+        let total = self.total_question_weight();
+        self.recovery_policy.answer_threshold.map_or(total, |v| total.min(v))
+    }
+
+    pub(crate) fn set_guardian_weight(&mut self, guardian: Guardian) {
+        match self.guardians.iter_mut().find(|v| v.account_id == guardian.account_id) {
+            Some(v) => v.weight = guardian.weight,
+            None => self.guardians.push(guardian),
+        }
+    }
+
+    pub(crate) fn remove_guardian_account(&mut self, account_id: AccountId) {
+        let position = self.guardians.iter().position(|v| v.account_id == account_id).expect("Guardian not found");
+        self.guardians.remove(position);
+        self.approvals.retain(|v| v.guardian != account_id);
+    }
+
+    /// Starts a recovery to `new_owner`, or checks it is the one in progress
+    pub(crate) fn start_recovery(&mut self, new_owner: AccountId) {
+        if let Some(current) = &self.new_owner {
+            assert!(current == &new_owner, "Invalid new_owner_account");
+        } else {
+            self.new_owner = Some(new_owner);
+        }
+    }
+
+    /// Hands the account to the new owner and resets the contract once the policy is met
+    pub(crate) fn finish_recovery_if_complete(&mut self) {
+        let Some(new_owner) = self.new_owner.clone() else {
+            return;
+        };
+        let required_weight = self.required_answer_weight();
+        let threshold = self.recovery_policy.guardian_threshold;
+        if (required_weight == 0 && threshold == 0)
+            || self.recovered_weight < required_weight
+            || self.get_approved_weight(new_owner.clone()) < threshold
+        {
+            return;
+        }
+
+        self.owner_id = new_owner;
+        self.tree.clear();
+        self.questions.clear();
+        self.reset_recovery();
+        self.queue.clear();
+    }
+
+    fn reset_recovery(&mut self) {
+        self.new_owner = None;
+        self.recovers.clear();
+        self.recovered_questions.clear();
+        self.recovered_weight = 0;
+        self.approvals.clear();
+    }
+
+    fn guardian_weight(&self, account_id: &AccountId) -> Option<u32> {
+        self.guardians.iter().find(|v| &v.account_id == account_id).map(|v| v.weight)
+    }
+}
@@ -1,142 +1,228 @@
-use std::{
-    fmt::Debug,
-    str::FromStr,
-    iter::{once, repeat, successors},
-};
+use std::fmt::Debug;
 
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    near_bindgen, PanicOnDefault, require, AccountId, env
+    near_bindgen, PanicOnDefault, require, AccountId, env, json_types::U64
 };
 use near_sdk::serde::{Serialize, Deserialize};
 
-use ark_groth16::{VerifyingKey, Proof};
-use ark_bn254::{Fr, Fq, Fq2, G1Affine, G2Affine, G1Projective, G2Projective, Bn254};
-use ark_ff::biginteger::BigInteger256;
-
-mod proof;
-mod verification_key;
+use ark_bn254::Fr;
 
-mod poseidon;
-mod merkle_tree;
-mod utils;
+mod queue;
+mod guardian;
+mod migrate;
 
+pub use near_zk_core::*;
+pub use queue::*;
+pub use guardian::*;
+pub use migrate::*;
 
-pub use proof::*;
-pub use verification_key::*;
+/// Default minimum time between owner-direct question replacements, one day in nanoseconds
+pub const DEFAULT_REPLACE_COOLDOWN: u64 = 24 * 60 * 60 * 1_000_000_000;
 
-pub use poseidon::*;
-pub use merkle_tree::*;
-pub use utils::*;
+/// Default delay of queued owner changes, two days in nanoseconds for a recovering account to cancel them
+pub const DEFAULT_QUEUE_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
     pub update_verification_key: String,
     pub recover_verification_key: String,
+    /// Key of the `recover_aggregate` circuit, empty until set with `set_verification_key`
+    pub recover_aggregate_verification_key: String,
     pub owner_id: AccountId,
     pub tree: MerkleTree<PoseidonHash>,
-    pub questions: Vec<String>,
+    pub questions: Vec<Option<Question>>,
     pub recovers: Vec<String>,
-    pub new_owner: Option<AccountId>
+    /// Questions proven in the current recovery, by proofs that carry the question signal
+    pub recovered_questions: Vec<String>,
+    /// Summed weight of the recover proofs of the current recovery
+    pub recovered_weight: u32,
+    pub new_owner: Option<AccountId>,
+    pub replace_cooldown: u64,
+    pub last_replaced_at: u64,
+    pub queue_delay: u64,
+    pub queue: Vec<QueuedOperation>,
+    pub next_queue_id: u64,
+    pub guardians: Vec<Guardian>,
+    pub recovery_policy: RecoveryPolicy,
+    pub approvals: Vec<Approval>
 }
 
 #[near_bindgen]
 impl Contract {
+    /// `queue_delay` defaults to `DEFAULT_QUEUE_DELAY`, zero applies owner changes immediately.
     #[init]
-    pub fn new(depth: usize, update_verification_key: String, recover_verification_key: String) -> Self {
+    pub fn new(
+        depth: usize,
+        update_verification_key: String,
+        recover_verification_key: String,
+        secondary_commitment: Option<ByteHashKind>,
+        queue_delay: Option<U64>
+    ) -> Self {
+        let mut tree = MerkleTree::new(depth, U256::zero());
+        if let Some(kind) = secondary_commitment {
+            tree = tree.with_secondary(kind);
+        }
         Self {
             update_verification_key,
             recover_verification_key,
+            recover_aggregate_verification_key: String::new(),
             owner_id: env::predecessor_account_id(),
-            tree: MerkleTree::new(depth, U256::zero()),
+            tree,
             questions: Vec::new(),
             recovers: Vec::new(),
-            new_owner: None
+            recovered_questions: Vec::new(),
+            recovered_weight: 0,
+            new_owner: None,
+            replace_cooldown: DEFAULT_REPLACE_COOLDOWN,
+            last_replaced_at: 0,
+            queue_delay: queue_delay.map_or(DEFAULT_QUEUE_DELAY, |v| v.0),
+            queue: Vec::new(),
+            next_queue_id: 0,
+            guardians: Vec::new(),
+            recovery_policy: RecoveryPolicy::default(),
+            approvals: Vec::new()
         }
     }
 
 
-    pub fn add_security_question(&mut self, question: String, leaf: String) {
-        assert!(self.owner_id == env::predecessor_account_id(), "Not onwer");
-        assert!(self.tree.num_leaves() > self.questions.len(), "Questions exceeds upper limit");
-        self.tree.set(self.questions.len(), U256::from_str_radix(leaf.as_str(), 10).unwrap());
-        self.questions.push(question);
+    /// Owner changes below apply immediately when the queue delay is zero,
+    /// otherwise they are queued and the queued operation id is returned.
+    /// When `text` is given it must encode to `question` with `QuestionCodec` and is stored with it.
+    /// `scheme` and `normalization` record how the leaf was computed and default to the legacy behavior.
+    /// `weight` is what a recover proof of the question counts toward the recovery policy, 1 by default.
+    pub fn add_security_question(
+        &mut self,
+        question: String,
+        leaf: String,
+        text: Option<String>,
+        scheme: Option<LeafScheme>,
+        normalization: Option<NormalizationProfile>,
+        weight: Option<u32>
+    ) -> Option<U64> {
+        let question = Question::new(question, text)
+            .with_scheme(scheme.unwrap_or_default())
+            .with_normalization(normalization.unwrap_or_default())
+            .with_weight(weight.unwrap_or(1));
+        self.schedule(QueuedAction::AddSecurityQuestion { question, leaf })
+    }
+
+    /// Removes a question and resets its leaf to the empty value.
+    /// With `compact`, the last question is moved into the freed slot so leaf indices stay dense.
+    pub fn remove_security_question(&mut self, question: String, compact: bool) -> Option<U64> {
+        self.schedule(QueuedAction::RemoveSecurityQuestion { question, compact })
+    }
+
+    /// Replaces the question at `index` without a proof.
+    /// Only one replacement is allowed per cooldown period, so a stolen owner key cannot swap out every question at once.
+    /// The new question keeps the weight of the replaced one.
+    pub fn replace_security_question(
+        &mut self,
+        index: usize,
+        question: String,
+        leaf: String,
+        text: Option<String>,
+        scheme: Option<LeafScheme>,
+        normalization: Option<NormalizationProfile>
+    ) -> Option<U64> {
+        let question = Question::new(question, text)
+            .with_scheme(scheme.unwrap_or_default())
+            .with_normalization(normalization.unwrap_or_default());
+        self.schedule(QueuedAction::ReplaceSecurityQuestion { index, question, leaf })
+    }
+
+    /// Raising the cooldown takes effect immediately, lowering it is queued and counts as a replacement.
+    pub fn set_replace_cooldown(&mut self, cooldown: U64) -> Option<U64> {
+        self.assert_owner();
+        if cooldown.0 >= self.replace_cooldown {
+            self.replace_cooldown = cooldown.0;
+            None
+        } else {
+            self.schedule(QueuedAction::SetReplaceCooldown { cooldown })
+        }
     }
 
-    pub fn update_security_question(&mut self, proof_string: String, public_input_string: String) {
-        assert!(self.recovers.is_empty(), "In recover");
+    pub fn update_security_question(
+        &mut self,
+        proof_string: String,
+        public_input_string: String,
+        new_question_text: Option<String>,
+        new_scheme: Option<LeafScheme>,
+        new_normalization: Option<NormalizationProfile>
+    ) {
+        self.assert_not_in_recovery();
 
-        let public_input_vec: Vec<String> = serde_json_wasm::from_str(&public_input_string).expect("Invalid public input");
+        let public_input_vec = parse_public_input(&public_input_string);
         let new_leaf = public_input_vec[0].clone();
         let old_root = public_input_vec[1].clone();
         let old_question = public_input_vec[2].clone();
-        let new_question = public_input_vec[3].clone();
+        let new_question = Question::new(public_input_vec[3].clone(), new_question_text)
+            .with_scheme(new_scheme.unwrap_or_default())
+            .with_normalization(new_normalization.unwrap_or_default());
 
         assert!(self.tree.root().to_string() == old_root, "Invalid proof: old root");
         self.verify(proof_string, public_input_string, "update".to_string());
-        let index = self.questions.iter().position(|v| v == &old_question).expect("Invalid proof: old question");
+        let index = self.question_index(&old_question).expect("Invalid proof: old question");
+        self.assert_question_free(&new_question.value, Some(index));
+        let weight = self.question_weight(index);
         self.tree.set(index, U256::from_str_radix(new_leaf.as_str(), 10).unwrap());
-        self.questions[index] = new_question;
+        self.questions[index] = Some(new_question.with_weight(weight));
     }
 
+    /// Public inputs are `[nullifier, root, question, new_owner]`, and the proof counts the weight of its question.
+    /// Proofs of the original circuit without the question signal are only accepted while all questions weigh the same.
     pub fn recover(&mut self, proof_string: String, public_input_string: String) {
-        let public_input_vec: Vec<String> = serde_json_wasm::from_str(&public_input_string).expect("Invalid public input");
-        let nullifier = public_input_vec[0].clone();
-        let root = public_input_vec[1].clone();
-        let new_owner_string = public_input_vec[2].clone();
-
-        let mut new_owner_bytes = [0u8; 32];
-        let new_owner_u256 = U256::from_str_radix(&new_owner_string, 10).unwrap();
-        new_owner_u256.to_big_endian(&mut new_owner_bytes);
-        let new_owner_utf8 = String::from_utf8(new_owner_bytes.to_vec()).unwrap().replace("\0", "");
-        let new_owner_account: AccountId = new_owner_utf8.parse().unwrap();
-
-        if let Some(new_owner) = &self.new_owner {
-            assert!(new_owner == &new_owner_account, "Invalid new_owner_account");
-        } else {
-            self.new_owner = Some(new_owner_account);
-        }
-        assert!(!self.recovers.contains(&nullifier), "Repeat recover");
+        let public_input_vec = parse_public_input(&public_input_string);
+        let (nullifier, root, question, new_owner) = match public_input_vec.as_slice() {
+            [nullifier, root, new_owner] => (nullifier.clone(), root.clone(), None, new_owner),
+            [nullifier, root, question, new_owner] => (nullifier.clone(), root.clone(), Some(question.clone()), new_owner),
+            _ => panic!("Invalid public input")
+        };
+
+        self.start_recovery(parse_new_owner(new_owner));
+        self.add_recover(nullifier, question);
         assert!(self.tree.root() == U256::from_str_radix(&root, 10).unwrap(), "Invalid proof: root");
 
         self.verify(proof_string, public_input_string, "recover".to_string());
-        self.recovers.push(nullifier);
+        self.finish_recovery_if_complete();
+    }
 
-        if self.questions.len() == self.recovers.len() {
-            let new_owner = self.new_owner.take().unwrap();
-            self.owner_id = new_owner;
-            let depth = self.tree.depth();
-            self.tree = MerkleTree::new(depth, U256::zero());
-            self.questions.clear();
-            self.recovers.clear();
+    /// Recovers with one proof of answers to several distinct questions.
+    /// Public inputs are `[nullifier_0, .., nullifier_k-1, root, question_0, .., question_k-1, new_owner]`.
+    pub fn recover_aggregate(&mut self, proof_string: String, public_input_string: String) {
+        assert!(!self.recover_aggregate_verification_key.is_empty(), "Aggregate recovery not enabled");
+        let public_input_vec = parse_public_input(&public_input_string);
+        assert!(public_input_vec.len() >= 4 && public_input_vec.len().is_multiple_of(2), "Invalid public input");
+        let count = (public_input_vec.len() - 2) / 2;
+        let (nullifiers, rest) = public_input_vec.split_at(count);
+        let (root, questions, new_owner) = (&rest[0], &rest[1..=count], &rest[count + 1]);
+
+        self.start_recovery(parse_new_owner(new_owner));
+        for (nullifier, question) in nullifiers.iter().zip(questions) {
+            self.add_recover(nullifier.clone(), Some(question.clone()));
         }
+        assert!(self.tree.root() == U256::from_str_radix(root, 10).unwrap(), "Invalid proof: root");
+
+        self.verify(proof_string, public_input_string, "recover_aggregate".to_string());
+        self.finish_recovery_if_complete();
     }
 
     pub fn verify(&self, proof_string: String, public_input_string: String, proof_type: String){
         let verification_key = match proof_type.as_str() {
             "update" => self.update_verification_key.clone(),
             "recover" => self.recover_verification_key.clone(),
+            "recover_aggregate" => self.recover_aggregate_verification_key.clone(),
             _ => panic!("proof_type error")
         };
-        let circom_verification_key: CircomVerificationKey = serde_json_wasm::from_str(&verification_key).expect("Invalid verification key");
-        assert_eq!(circom_verification_key.protocol, "groth16");
-        let verification_key: VerifyingKey<Bn254> = circom_verification_key.into();
-        let prepare_verifying_key = ark_groth16::prepare_verifying_key(&verification_key);
-
-        let circom_proof: CircomProof = serde_json_wasm::from_str(&proof_string).expect("Invalid proof");
-        assert_eq!(circom_proof.protocol, "groth16");
-        let proof: Proof<Bn254> = circom_proof.into();
-
-        let circom_public_input: Vec<String> = serde_json_wasm::from_str(&public_input_string).expect("Invalid public input");
-        let pub_inputs: Vec<Fr> = circom_public_input.into_iter().map(|v| Fr::from_str(&v).unwrap()).collect();
-        require!(ark_groth16::verify_proof(&prepare_verifying_key, &proof, &pub_inputs[..]).unwrap());
+        let verified = verify_circom_proof(&verification_key, &proof_string, &public_input_string).unwrap_or_else(|e| env::panic_str(&e));
+        require!(verified);
     }
 
     pub fn get_proof_path(&self, question: String) -> Option<(String, Vec<String>, Vec<String>)> {
         let mut path_indices = vec![];
         let mut siblings = vec![];
-        if let Some(index) = self.questions.iter().position(|v| v == &question) {
+        if let Some(index) = self.question_index(&question) {
             if let Some(path) = self.tree.proof(index) {
                 for item in path.0.iter() {
                     match item {
@@ -160,14 +246,185 @@ impl Contract {
     }
 
     pub fn get_questions(&self) -> Vec<String> {
-        self.questions.clone()
+        self.questions.iter().flatten().map(|v| v.value.clone()).collect()
+    }
+
+    /// Readable text of each question, `None` for long questions added without their text
+    pub fn get_question_texts(&self) -> Vec<Option<String>> {
+        self.questions.iter().flatten().map(Question::readable_text).collect()
+    }
+
+    pub fn get_question_text(&self, question: String) -> Option<String> {
+        let index = self.question_index(&question)?;
+        self.questions[index].as_ref().and_then(Question::readable_text)
+    }
+
+    pub fn get_question_records(&self) -> Vec<Question> {
+        self.questions.iter().flatten().cloned().collect()
     }
 
     pub fn get_recovers(&self) -> Vec<String> {
         self.recovers.clone()
     }
 
+    /// Summed weight of the questions proven in the current recovery
+    pub fn get_recovered_weight(&self) -> u32 {
+        self.recovered_weight
+    }
+
     pub fn get_owner(&self) -> AccountId {
         self.owner_id.clone()
     }
+
+    pub fn get_tree_mode(&self) -> TreeMode {
+        self.tree.mode()
+    }
+
+    /// Root of the Keccak-256 or SHA-256 commitment to the same leaves, if configured
+    pub fn get_secondary_root(&self) -> Option<H256> {
+        self.tree.secondary().map(SecondaryTree::root)
+    }
+
+    pub fn get_replace_cooldown(&self) -> U64 {
+        self.replace_cooldown.into()
+    }
+
+    pub fn get_last_replaced_at(&self) -> U64 {
+        self.last_replaced_at.into()
+    }
+}
+
+/// Public inputs as canonical decimal field elements. The verifier reduces its inputs modulo the field,
+/// so values beyond it would let one proof pass again under another nullifier.
+fn parse_public_input(public_input_string: &str) -> Vec<String> {
+    let public_input_vec: Vec<String> = serde_json_wasm::from_str(public_input_string).expect("Invalid public input");
+    public_input_vec
+        .iter()
+        .map(|v| {
+            let value = U256::from_dec_str(v).ok().filter(|value| !v.is_empty() && Fr::try_from(*value).is_ok());
+            value.expect("Invalid public input").to_string()
+        })
+        .collect()
+}
+
+fn parse_new_owner(value: &str) -> AccountId {
+    let value = U256::from_str_radix(value, 10).expect("Invalid new_owner_account");
+    decode_account_id(value).expect("Invalid new_owner_account")
+}
+
+/// Decodes a `new_owner` signal, ignoring NUL bytes
+pub fn decode_account_id(value: U256) -> Option<AccountId> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    String::from_utf8(bytes.to_vec()).ok()?.replace('\0', "").parse().ok()
+}
+
+impl Contract {
+    fn assert_owner(&self) {
+        assert!(self.owner_id == env::predecessor_account_id(), "Not onwer");
+    }
+
+    /// Owner changes wait while recover proofs or guardian approvals are pending
+    fn assert_not_in_recovery(&self) {
+        assert!(self.new_owner.is_none() && self.recovers.is_empty() && self.approvals.is_empty(), "In recover");
+    }
+
+    fn insert_question(&mut self, question: Question, leaf: String) {
+        self.assert_question_free(&question.value, None);
+        let index = self.questions.iter().position(Option::is_none).unwrap_or(self.questions.len());
+        assert!(self.tree.num_leaves() > index, "Questions exceeds upper limit");
+        self.tree.set(index, U256::from_str_radix(leaf.as_str(), 10).unwrap());
+        if index == self.questions.len() {
+            self.questions.push(Some(question));
+        } else {
+            self.questions[index] = Some(question);
+        }
+    }
+
+    fn remove_question(&mut self, question: String, compact: bool) {
+        self.assert_not_in_recovery();
+
+        let index = self.question_index(&question).expect("Question not found");
+        let last = self.questions.iter().rposition(Option::is_some).unwrap();
+        if compact && index != last {
+            self.tree.move_leaf(last, index);
+            self.questions.swap(index, last);
+            self.questions[last] = None;
+        } else {
+            self.tree.reset(index);
+            self.questions[index] = None;
+        }
+        while let Some(None) = self.questions.last() {
+            self.questions.pop();
+        }
+    }
+
+    fn replace_question(&mut self, index: usize, question: Question, leaf: String) {
+        self.assert_not_in_recovery();
+        assert!(matches!(self.questions.get(index), Some(Some(_))), "Question not found");
+        self.assert_cooled_down();
+        self.assert_question_free(&question.value, Some(index));
+
+        let weight = self.question_weight(index);
+        self.tree.set(index, U256::from_str_radix(leaf.as_str(), 10).unwrap());
+        self.questions[index] = Some(question.with_weight(weight));
+        self.last_replaced_at = env::block_timestamp();
+    }
+
+    fn apply_replace_cooldown(&mut self, cooldown: u64) {
+        if cooldown < self.replace_cooldown {
+            self.assert_cooled_down();
+            self.last_replaced_at = env::block_timestamp();
+        }
+        self.replace_cooldown = cooldown;
+    }
+
+    fn assert_cooled_down(&self) {
+        assert!(
+            env::block_timestamp() >= self.last_replaced_at.saturating_add(self.replace_cooldown),
+            "In cooldown"
+        );
+    }
+
+    /// Checks no question other than the one at `index` has this value, as a recovery counts each question once
+    fn assert_question_free(&self, question: &str, index: Option<usize>) {
+        let existing = self.question_index(question);
+        assert!(existing.is_none() || existing == index, "Question exists");
+    }
+
+    /// Leaf index of a question
+    fn question_index(&self, question: &str) -> Option<usize> {
+        self.questions.iter().position(|v| v.as_ref().map(|v| v.value.as_str()) == Some(question))
+    }
+
+    /// Records a proven nullifier and adds the weight of its question, see `recover`
+    fn add_recover(&mut self, nullifier: String, question: Option<String>) {
+        assert!(!self.recovers.contains(&nullifier), "Repeat recover");
+        let weight = match &question {
+            Some(question) => {
+                assert!(!self.recovered_questions.contains(question), "Repeat recover");
+                let index = self.question_index(question).expect("Invalid proof: question");
+                self.question_weight(index)
+            }
+            None => {
+                // The proof could be of any question, so it only counts while they all weigh the same
+                let mut weights = self.questions.iter().flatten().map(|v| v.weight);
+                let weight = weights.next().unwrap_or(0);
+                assert!(weights.all(|v| v == weight), "Weighted questions need the question signal");
+                weight
+            }
+        };
+        self.recovers.push(nullifier);
+        self.recovered_questions.extend(question);
+        self.recovered_weight += weight;
+    }
+
+    fn question_weight(&self, index: usize) -> u32 {
+        self.questions[index].as_ref().map_or(0, |v| v.weight)
+    }
+
+    /// Summed weight of the questions currently set
+    fn total_question_weight(&self) -> u32 {
+        self.questions.iter().flatten().map(|v| v.weight).sum()
+    }
 }
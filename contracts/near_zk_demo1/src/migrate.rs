@@ -0,0 +1,45 @@
+use crate::*;
+
+/// Stored layout of the first contract version
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LegacyContract {
+    pub update_verification_key: String,
+    pub recover_verification_key: String,
+    pub owner_id: AccountId,
+    pub tree: LegacyMerkleTree<PoseidonHash>,
+    pub questions: Vec<String>,
+    pub recovers: Vec<String>,
+    pub new_owner: Option<AccountId>
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Upgrades the state of the first contract version after deploying this code.
+    /// Questions keep their legacy leaves with weight 1, a recovery in progress keeps its proofs,
+    /// and owner changes are queued with `DEFAULT_QUEUE_DELAY`.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: LegacyContract = env::state_read().expect("No state to migrate");
+        Self {
+            update_verification_key: old.update_verification_key,
+            recover_verification_key: old.recover_verification_key,
+            recover_aggregate_verification_key: String::new(),
+            owner_id: old.owner_id,
+            tree: old.tree.into(),
+            questions: old.questions.into_iter().map(|v| Some(Question::new(v, None))).collect(),
+            recovered_questions: Vec::new(),
+            recovered_weight: old.recovers.len() as u32,
+            recovers: old.recovers,
+            new_owner: old.new_owner,
+            replace_cooldown: DEFAULT_REPLACE_COOLDOWN,
+            last_replaced_at: 0,
+            queue_delay: DEFAULT_QUEUE_DELAY,
+            queue: Vec::new(),
+            next_queue_id: 0,
+            guardians: Vec::new(),
+            recovery_policy: RecoveryPolicy::default(),
+            approvals: Vec::new()
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::*;
+
+/// Owner change to the question set, guardians, verification keys or recovery settings
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum QueuedAction {
+    AddSecurityQuestion { question: Question, leaf: String },
+    RemoveSecurityQuestion { question: String, compact: bool },
+    ReplaceSecurityQuestion { index: usize, question: Question, leaf: String },
+    SetVerificationKey { proof_type: String, verification_key: String },
+    SetReplaceCooldown { cooldown: U64 },
+    SetQueueDelay { delay: U64 },
+    SetGuardian { guardian: Guardian },
+    RemoveGuardian { account_id: AccountId },
+    SetRecoveryPolicy { policy: RecoveryPolicy },
+}
+
+/// Action waiting in the queue until `execute_after`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QueuedOperation {
+    pub id: U64,
+    pub action: QueuedAction,
+    pub execute_after: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn set_verification_key(&mut self, proof_type: String, verification_key: String) -> Option<U64> {
+        assert!(matches!(proof_type.as_str(), "update" | "recover" | "recover_aggregate"), "proof_type error");
+        let _: CircomVerificationKey = serde_json_wasm::from_str(&verification_key).expect("Invalid verification key");
+        self.schedule(QueuedAction::SetVerificationKey { proof_type, verification_key })
+    }
+
+    /// Raising the delay takes effect immediately, lowering it goes through the queue.
+    pub fn set_queue_delay(&mut self, delay: U64) -> Option<U64> {
+        self.assert_owner();
+        if delay.0 >= self.queue_delay {
+            self.queue_delay = delay.0;
+            None
+        } else {
+            self.schedule(QueuedAction::SetQueueDelay { delay })
+        }
+    }
+
+    pub fn execute_queued(&mut self, id: U64) {
+        self.assert_owner();
+        self.assert_not_in_recovery();
+        let position = self.queue.iter().position(|v| v.id == id).expect("Queued operation not found");
+        assert!(env::block_timestamp() >= self.queue[position].execute_after.0, "Queued operation not ready");
+        let operation = self.queue.remove(position);
+        self.apply(operation.action);
+    }
+
+    /// Cancels a queued operation. Callable by the owner, or by the new owner of an active recovery.
+    pub fn cancel_queued(&mut self, id: U64) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller == self.owner_id || self.new_owner.as_ref() == Some(&caller),
+            "Not owner or recovering account"
+        );
+        let position = self.queue.iter().position(|v| v.id == id).expect("Queued operation not found");
+        self.queue.remove(position);
+    }
+
+    pub fn get_queued_operations(&self) -> Vec<QueuedOperation> {
+        self.queue.clone()
+    }
+
+    pub fn get_queued_operation(&self, id: U64) -> Option<QueuedOperation> {
+        self.queue.iter().find(|v| v.id == id).cloned()
+    }
+
+    pub fn get_queue_delay(&self) -> U64 {
+        self.queue_delay.into()
+    }
+}
+
+impl Contract {
+    /// Applies an owner action right away when there is no queue delay,
+    /// otherwise queues it and returns its id.
+    pub(crate) fn schedule(&mut self, action: QueuedAction) -> Option<U64> {
+        self.assert_owner();
+        if self.queue_delay == 0 {
+            self.apply(action);
+            return None;
+        }
+
+        let id = U64(self.next_queue_id);
+        self.next_queue_id += 1;
+        self.queue.push(QueuedOperation {
+            id,
+            action,
+            execute_after: env::block_timestamp().saturating_add(self.queue_delay).into(),
+        });
+        Some(id)
+    }
+
+    fn apply(&mut self, action: QueuedAction) {
+        match action {
+            QueuedAction::AddSecurityQuestion { question, leaf } => self.insert_question(question, leaf),
+            QueuedAction::RemoveSecurityQuestion { question, compact } => self.remove_question(question, compact),
+            QueuedAction::ReplaceSecurityQuestion { index, question, leaf } => self.replace_question(index, question, leaf),
+            QueuedAction::SetVerificationKey { proof_type, verification_key } => match proof_type.as_str() {
+                "update" => self.update_verification_key = verification_key,
+                "recover" => self.recover_verification_key = verification_key,
+                "recover_aggregate" => self.recover_aggregate_verification_key = verification_key,
+                _ => panic!("proof_type error")
+            },
+            QueuedAction::SetReplaceCooldown { cooldown } => self.apply_replace_cooldown(cooldown.0),
+            QueuedAction::SetQueueDelay { delay } => self.queue_delay = delay.0,
+            QueuedAction::SetGuardian { guardian } => self.set_guardian_weight(guardian),
+            QueuedAction::RemoveGuardian { account_id } => self.remove_guardian_account(account_id),
+            QueuedAction::SetRecoveryPolicy { policy } => self.recovery_policy = policy,
+        }
+    }
+}
@@ -21,14 +21,17 @@ pub async fn initialize_contracts_and_users(
 
     let update_verification_key_string = std::fs::read_to_string("../../circuits/out/update_verification_key.json")
         .expect("Invalid withdraw verification key file path");
-    let recover_verification_key_string = std::fs::read_to_string("../../circuits/out/recover_verification_key.json")
+    // The recover proofs in tests/data are of `circuits/recover_legacy.circom`
+    let recover_verification_key_string = std::fs::read_to_string("../../circuits/out/recover_legacy_verification_key.json")
         .expect("Invalid split verification key file path");
 
     near_zk_demo1_contract.call(worker, "new")
         .args_json(json!({
             "depth": depth,
             "update_verification_key": update_verification_key_string,
-            "recover_verification_key": recover_verification_key_string, 
+            "recover_verification_key": recover_verification_key_string,
+            // The scene changes questions right away
+            "queue_delay": "0",
         }))?
         .gas(300_000_000_000_000)
         .transact()
@@ -0,0 +1,302 @@
+use ark_bn254::{Fr, FrParameters, G1Affine, G2Affine};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{BigInteger256, Field, FpParameters, One, PrimeField, ToBytes, UniformRand};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_relations::r1cs::{ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use near_sdk::serde_json;
+use near_zk_demo1::*;
+use near_zk_prover::*;
+
+fn circuits_dir() -> String {
+    format!("{}/../../circuits", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Matrices and full assignment of the native update circuit, in circom wire order
+fn update_circuit() -> (ConstraintMatrices<Fr>, Vec<Fr>) {
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(format!("{}/update_input.json", circuits_dir())).unwrap()).unwrap();
+    let path = serde_json::json!([json["root"], json["pathIndices"], json["siblings"]]);
+    let input = UpdateInput::new(
+        &PathSource::from_proof_path_json(&path.to_string()).unwrap(),
+        &AnswerInput::new("What's your favorite food", "ice cream"),
+        &AnswerInput::new("which season do you like", "autumn"),
+    )
+    .unwrap();
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    UpdateCircuit { levels: 2, max_rounds: 0, input: Some(input) }.generate_constraints(cs.clone()).unwrap();
+    cs.finalize();
+    let matrices = cs.to_matrices().unwrap();
+    let cs = cs.borrow().unwrap();
+    let witness = cs.instance_assignment.iter().chain(&cs.witness_assignment).copied().collect();
+    (matrices, witness)
+}
+
+fn write_u32(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+fn write_bigint(out: &mut Vec<u8>, value: BigInteger256) {
+    value.write(&mut *out).unwrap();
+}
+
+fn write_file(magic: &[u8], sections: Vec<(u32, Vec<u8>)>) -> Vec<u8> {
+    let mut out = magic.to_vec();
+    write_u32(&mut out, 1);
+    write_u32(&mut out, sections.len());
+    for (kind, data) in sections {
+        out.extend_from_slice(&kind.to_le_bytes());
+        out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        out.extend(data);
+    }
+    out
+}
+
+fn field_header(n: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_u32(&mut out, 32);
+    write_bigint(&mut out, FrParameters::MODULUS);
+    write_u32(&mut out, n);
+    out
+}
+
+fn write_r1cs(matrices: &ConstraintMatrices<Fr>) -> Vec<u8> {
+    let mut header = field_header(matrices.num_instance_variables + matrices.num_witness_variables);
+    write_u32(&mut header, matrices.num_instance_variables - 1);
+    write_u32(&mut header, 0);
+    write_u32(&mut header, 0);
+    header.extend_from_slice(&0u64.to_le_bytes());
+    write_u32(&mut header, matrices.num_constraints);
+
+    let mut constraints = Vec::new();
+    for i in 0..matrices.num_constraints {
+        for row in [&matrices.a[i], &matrices.b[i], &matrices.c[i]] {
+            write_u32(&mut constraints, row.len());
+            for (coeff, wire) in row {
+                write_u32(&mut constraints, *wire);
+                write_bigint(&mut constraints, coeff.into_repr());
+            }
+        }
+    }
+    write_file(b"r1cs", vec![(1, header), (2, constraints)])
+}
+
+fn write_wtns(witness: &[Fr]) -> Vec<u8> {
+    let mut values = Vec::new();
+    for value in witness {
+        write_bigint(&mut values, value.into_repr());
+    }
+    write_file(b"wtns", vec![(1, field_header(witness.len())), (2, values)])
+}
+
+/// Montgomery form coordinates, zeros at infinity like snarkjs
+fn write_g1(out: &mut Vec<u8>, point: G1Affine) {
+    if point.infinity {
+        return out.extend_from_slice(&[0; 64]);
+    }
+    write_bigint(out, point.x.0);
+    write_bigint(out, point.y.0);
+}
+
+fn write_g2(out: &mut Vec<u8>, point: G2Affine) {
+    if point.infinity {
+        return out.extend_from_slice(&[0; 128]);
+    }
+    for value in [point.x.c0, point.x.c1, point.y.c0, point.y.c1] {
+        write_bigint(out, value.0);
+    }
+}
+
+/// `.zkey` laid out like `snarkjs groth16 setup`, from a known toxic waste with `gamma = 1`
+fn write_zkey(matrices: &ConstraintMatrices<Fr>, rng: &mut StdRng) -> Vec<u8> {
+    let [tau, alpha, beta, delta] = [(); 4].map(|_| Fr::rand(rng));
+    let g1 = |v: Fr| G1Affine::prime_subgroup_generator().mul(v).into_affine();
+    let g2 = |v: Fr| G2Affine::prime_subgroup_generator().mul(v).into_affine();
+
+    let n_public = matrices.num_instance_variables - 1;
+    let n_vars = matrices.num_instance_variables + matrices.num_witness_variables;
+    let domain = Radix2EvaluationDomain::<Fr>::new(matrices.num_constraints + n_public + 1).unwrap();
+    let domain_size = domain.size();
+    let lagrange = domain.evaluate_all_lagrange_coefficients(tau);
+    let lagrange2 = Radix2EvaluationDomain::<Fr>::new(2 * domain_size).unwrap().evaluate_all_lagrange_coefficients(tau);
+
+    // Public wires get an extra `A` row each, which snarkjs stores with the coefficients
+    let mut a_rows = matrices.a.clone();
+    a_rows.extend((0..=n_public).map(|s| vec![(Fr::one(), s)]));
+    let evaluate = |rows: &[Vec<(Fr, usize)>]| {
+        let mut values = vec![Fr::from(0u64); n_vars];
+        for (row, l) in rows.iter().zip(&lagrange) {
+            for (coeff, wire) in row {
+                values[*wire] += *coeff * l;
+            }
+        }
+        values
+    };
+    let (a, b, c) = (evaluate(&a_rows), evaluate(&matrices.b), evaluate(&matrices.c));
+    let ic = |i: usize| beta * a[i] + alpha * b[i] + c[i];
+    let delta_inverse = delta.inverse().unwrap();
+
+    let mut header = Vec::new();
+    write_u32(&mut header, 32);
+    write_bigint(&mut header, ark_bn254::FqParameters::MODULUS);
+    write_u32(&mut header, 32);
+    write_bigint(&mut header, FrParameters::MODULUS);
+    write_u32(&mut header, n_vars);
+    write_u32(&mut header, n_public);
+    write_u32(&mut header, domain_size);
+    write_g1(&mut header, g1(alpha));
+    write_g1(&mut header, g1(beta));
+    write_g2(&mut header, g2(beta));
+    write_g2(&mut header, g2(Fr::one()));
+    write_g1(&mut header, g1(delta));
+    write_g2(&mut header, g2(delta));
+
+    let g1_section = |values: &mut dyn Iterator<Item = Fr>| {
+        let mut out = Vec::new();
+        values.for_each(|v| write_g1(&mut out, g1(v)));
+        out
+    };
+    let mut coefficients = Vec::new();
+    let entries = a_rows
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.iter().map(move |v| (0, i, v)))
+        .chain(matrices.b.iter().enumerate().flat_map(|(i, row)| row.iter().map(move |v| (1, i, v))))
+        .collect::<Vec<_>>();
+    write_u32(&mut coefficients, entries.len());
+    let r = Fr::from_repr(FrParameters::R).unwrap();
+    for (matrix, constraint, (coeff, wire)) in entries {
+        write_u32(&mut coefficients, matrix);
+        write_u32(&mut coefficients, constraint);
+        write_u32(&mut coefficients, *wire);
+        write_bigint(&mut coefficients, (*coeff * r).0);
+    }
+    let mut b2 = Vec::new();
+    b.iter().for_each(|v| write_g2(&mut b2, g2(*v)));
+
+    write_file(
+        b"zkey",
+        vec![
+            (1, 1u32.to_le_bytes().to_vec()),
+            (2, header),
+            (3, g1_section(&mut (0..=n_public).map(ic))),
+            (4, coefficients),
+            (5, g1_section(&mut a.iter().copied())),
+            (6, g1_section(&mut b.iter().copied())),
+            (7, b2),
+            (8, g1_section(&mut (n_public + 1..n_vars).map(|i| ic(i) * delta_inverse))),
+            (9, g1_section(&mut (0..domain_size).map(|i| lagrange2[2 * i + 1] * delta_inverse))),
+        ],
+    )
+}
+
+/// `.zkey` with a header count replaced, `0` for `n_vars`, `1` for `n_public`, `2` for the domain size
+fn patch_zkey_header(mut zkey: Vec<u8>, field: usize, value: u32) -> Vec<u8> {
+    // File and key type section headers, then the two field headers of the header section
+    let offset = 12 + 16 + 12 + 72 + 4 * field;
+    zkey[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    zkey
+}
+
+#[test]
+fn test_parse_r1cs_and_witness() {
+    let (matrices, witness) = update_circuit();
+    let r1cs = R1csFile::parse(&write_r1cs(&matrices)).unwrap();
+    assert_eq!(r1cs.n_wires, witness.len());
+    assert_eq!(r1cs.n_public(), 4);
+    assert_eq!(r1cs.constraints.len(), matrices.num_constraints);
+
+    let parsed = parse_witness(&write_wtns(&witness)).unwrap();
+    assert_eq!(parsed, witness);
+    assert!(r1cs.is_satisfied(&parsed));
+
+    let public: Vec<String> = serde_json::from_str(include_str!("data/update_public.json")).unwrap();
+    assert_eq!(public_signals(&parsed, 4).iter().map(U256::to_string).collect::<Vec<_>>(), public);
+
+    let mut wrong = parsed;
+    wrong[5] += Fr::one();
+    assert!(!r1cs.is_satisfied(&wrong));
+
+    assert!(R1csFile::parse(b"wtns").is_err());
+    assert!(parse_witness(&write_r1cs(&matrices)).is_err());
+}
+
+#[test]
+fn test_circom_circuit_proof() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let (matrices, witness) = update_circuit();
+    let r1cs = R1csFile::parse(&write_r1cs(&matrices)).unwrap();
+
+    let proving_key = setup(CircomCircuit { r1cs: r1cs.clone(), witness: None }, &mut rng).unwrap();
+    let proof = prove(CircomCircuit { r1cs, witness: Some(witness.clone()) }, &proving_key, &mut rng).unwrap();
+    let verifying_key = ark_groth16::prepare_verifying_key(&proving_key.vk);
+    assert!(ark_groth16::verify_proof(&verifying_key, &proof, &witness[1..5]).unwrap());
+}
+
+#[test]
+fn test_zkey_proof() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let (matrices, witness) = update_circuit();
+    let zkey = Zkey::parse(&write_zkey(&matrices, &mut rng)).unwrap();
+    assert_eq!(zkey.n_public, 4);
+
+    let proof = zkey.prove(&witness, &mut rng).unwrap();
+    let verifying_key = ark_groth16::prepare_verifying_key(zkey.verifying_key());
+    assert!(ark_groth16::verify_proof(&verifying_key, &proof, &witness[1..5]).unwrap());
+
+    let mut wrong = witness.clone();
+    wrong[1] += Fr::one();
+    assert!(!ark_groth16::verify_proof(&verifying_key, &proof, &wrong[1..5]).unwrap());
+    assert!(zkey.prove(&witness[1..], &mut rng).is_err());
+
+    // The proof survives the snarkjs JSON format
+    let json = serde_json::to_string(&CircomProof::from(proof.clone())).unwrap();
+    let parsed: CircomProof = serde_json::from_str(&json).unwrap();
+    assert_eq!(ark_groth16::Proof::from(parsed), proof);
+}
+
+#[test]
+fn test_zkey_invalid_header() {
+    let (matrices, _) = update_circuit();
+    let zkey = write_zkey(&matrices, &mut StdRng::seed_from_u64(1));
+    let error = |field: usize, value: u32| Zkey::parse(&patch_zkey_header(zkey.clone(), field, value)).unwrap_err().to_string();
+    let n_vars = matrices.num_instance_variables + matrices.num_witness_variables;
+    let domain_size = Zkey::parse(&zkey).unwrap().domain_size as u32;
+
+    assert_eq!(error(1, n_vars as u32), "Fewer variables than public signals");
+    assert_eq!(error(1, u32::MAX), "Fewer variables than public signals");
+    assert_eq!(error(0, n_vars as u32 + 1), "Section size differs from the header");
+    assert_eq!(error(2, domain_size + 1), "Invalid domain size");
+    assert_eq!(error(2, 0), "Invalid domain size");
+    assert_eq!(error(2, u32::MAX), "Invalid domain size");
+    assert_eq!(error(2, 1 << 28), "Invalid domain size");
+    // A power of two within the field, but more points than the file has
+    assert_eq!(error(2, 1 << 27), "Section size differs from the header");
+}
+
+/// Checks the artifacts of `make circuit` in `circuits/out`, run by `make test`
+#[test]
+#[ignore = "needs `make circuit`"]
+fn test_snarkjs_artifacts() {
+    let out = format!("{}/out", circuits_dir());
+    for name in ["update", "recover"] {
+        let read = |file: String| std::fs::read(format!("{}/{}", out, file)).expect("Run `make circuit` first");
+        let (r1cs, wtns, zkey) = (read(format!("{}.r1cs", name)), read(format!("{}_witness.wtns", name)), read(format!("{}_0000.zkey", name)));
+        let r1cs = R1csFile::parse(&r1cs).unwrap();
+        let witness = parse_witness(&wtns).unwrap();
+        assert!(r1cs.is_satisfied(&witness));
+
+        let zkey = Zkey::parse(&zkey).unwrap();
+        let proof = zkey.prove(&witness, &mut StdRng::seed_from_u64(2)).unwrap();
+        let signals = public_signals(&witness, r1cs.n_public());
+
+        // snarkjs exports the same key, and the proof JSON is accepted with it
+        let vk = String::from_utf8(read(format!("{}_verification_key.json", name))).unwrap();
+        assert_eq!(snarkjs_json(&CircomVerificationKey::from(zkey.verifying_key().clone())), vk);
+        let contract = Contract::new(3, vk.clone(), vk, None, None);
+        let proof = serde_json::to_string(&CircomProof::from(proof)).unwrap();
+        let signals = serde_json::to_string(&signals.iter().map(U256::to_string).collect::<Vec<_>>()).unwrap();
+        contract.verify(proof, signals, name.to_string());
+    }
+}
@@ -0,0 +1,152 @@
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde_json::{self, json, Value};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::AccountId;
+use near_zk_demo1::*;
+use near_zk_prover::*;
+
+// Questions of test_scene.rs, before and after the update of the first one
+const QUESTIONS: [(&str, &str); 3] = [
+    ("What's your favorite food", "ice cream"),
+    ("where are you from", "china"),
+    ("What's your favorite number", "666"),
+];
+const UPDATED_QUESTIONS: [(&str, &str); 3] = [
+    ("which season do you like", "autumn"),
+    ("where are you from", "china"),
+    ("What's your favorite number", "666"),
+];
+
+fn circuit_input(name: &str) -> Value {
+    let path = format!("{}/../../circuits/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn sample_contract(questions: &[(&str, &str)], hardened: Option<(usize, U256)>) -> Contract {
+    let mut tree = MerkleTree::<PoseidonHash>::new(3, U256::zero());
+    let mut records = Vec::new();
+    for (i, (question, answer)) in questions.iter().enumerate() {
+        let value = QuestionCodec::encode(question);
+        let mut record = Question::new(value.to_string(), Some(question.to_string()));
+        let mut leaf = hash2(value, U256::from_big_endian(answer.as_bytes()));
+        if let Some((index, salt)) = hardened.filter(|v| v.0 == i) {
+            let scheme = LeafScheme::Hardened { rounds: 16 };
+            record = record.with_scheme(scheme);
+            leaf = scheme.leaf(value, U256::from_big_endian(answer.as_bytes()), salt);
+            assert_eq!(index, i);
+        }
+        tree.set(i, leaf);
+        records.push(Some(record));
+    }
+    Contract {
+        update_verification_key: String::new(),
+        recover_verification_key: String::new(),
+        recover_aggregate_verification_key: String::new(),
+        owner_id: "alice.test.near".parse().unwrap(),
+        tree,
+        questions: records,
+        recovers: Vec::new(),
+        recovered_questions: Vec::new(),
+        recovered_weight: 0,
+        new_owner: None,
+        replace_cooldown: DEFAULT_REPLACE_COOLDOWN,
+        last_replaced_at: 0,
+        queue_delay: 0,
+        queue: Vec::new(),
+        next_queue_id: 0,
+        guardians: Vec::new(),
+        recovery_policy: RecoveryPolicy::default(),
+        approvals: Vec::new(),
+    }
+}
+
+fn state_dump(contract: &Contract) -> String {
+    let value = Base64VecU8::from(contract.try_to_vec().unwrap());
+    json!({ "values": [
+        { "key": Base64VecU8::from(b"OTHER".to_vec()), "value": Base64VecU8::from(vec![1]) },
+        { "key": Base64VecU8::from(b"STATE".to_vec()), "value": value },
+    ]})
+    .to_string()
+}
+
+#[test]
+fn test_account_id_encoding() {
+    let account: AccountId = "bob.test.near".parse().unwrap();
+    let value = encode_account_id(account.as_str()).unwrap();
+    assert_eq!(value, U256::from_dec_str("7798831456594387459371788755314").unwrap());
+    assert_eq!(decode_account_id(value), Some(account));
+
+    let long: AccountId = "a-very-long-account-name-for-testing.near".parse().unwrap();
+    assert!(encode_account_id(long.as_str()).is_err());
+    assert_eq!(encode_account_id("Bob.near").unwrap_err().0, "Invalid account id Bob.near");
+    assert!(encode_account_id("bob..near").is_err());
+}
+
+#[test]
+fn test_recover_input_from_proof_path() {
+    let contract = sample_contract(&UPDATED_QUESTIONS, None);
+    let path = contract.get_proof_path(QuestionCodec::encode("where are you from").to_string()).unwrap();
+    let source = PathSource::from_proof_path_json(&serde_json::to_string(&path).unwrap()).unwrap();
+
+    let new_owner = "bob.test.near";
+    let input = RecoverInput::new(&source, &AnswerInput::new("where are you from", "china"), new_owner).unwrap();
+    assert_eq!(serde_json::to_value(&input).unwrap(), circuit_input("recover_input.json"));
+    assert_eq!(input.public_signals()[0], hash1(U256::from_big_endian(b"china")));
+
+    let wrong = RecoverInput::new(&source, &AnswerInput::new("where are you from", "China"), new_owner);
+    assert_eq!(wrong.unwrap_err().0, "Answer does not match the stored leaf");
+
+    let mut answer = AnswerInput::new("where are you from", "  China ");
+    answer.normalization = Some(NormalizationProfile::Standard);
+    assert!(RecoverInput::new(&source, &answer, new_owner).is_ok());
+}
+
+#[test]
+fn test_inputs_from_state() {
+    let salt = U256::from_dec_str("319187461284017342158731264781237461982374619827346198234").unwrap();
+    let source = PathSource::from_state_json(&state_dump(&sample_contract(&QUESTIONS, Some((1, salt))))).unwrap();
+    let new_owner = "bob.test.near";
+
+    let mut answer = AnswerInput::new("where are you from", "china");
+    assert_eq!(RecoverInput::new(&source, &answer, new_owner).unwrap_err().0, "Hardened leaf needs a salt");
+    answer.salt = Some(salt);
+    let input = RecoverInput::new(&source, &answer, new_owner).unwrap();
+    assert_eq!(serde_json::to_value(&input).unwrap(), circuit_input("recover_hardened_input.json"));
+
+    let source = PathSource::from_state_json(&state_dump(&sample_contract(&QUESTIONS, None))).unwrap();
+    let input = UpdateInput::new(
+        &source,
+        &AnswerInput::new("What's your favorite food", "ice cream"),
+        &AnswerInput::new("which season do you like", "autumn"),
+    )
+    .unwrap();
+    assert_eq!(serde_json::to_value(&input).unwrap(), circuit_input("update_input.json"));
+    assert_eq!(input.new_leaf(), hash2(QuestionCodec::encode("which season do you like"), U256::from_big_endian(b"autumn")));
+
+    let missing = AnswerInput::new("What is your quest", "grail");
+    assert!(RecoverInput::new(&source, &missing, new_owner).is_err());
+    assert!(PathSource::from_state_json(r#"{"values": []}"#).is_err());
+}
+
+#[test]
+fn test_recover_aggregate_input() {
+    let salt = U256::from_dec_str("319187461284017342158731264781237461982374619827346198234").unwrap();
+    let source = PathSource::from_state_json(&state_dump(&sample_contract(&UPDATED_QUESTIONS, Some((1, salt))))).unwrap();
+    let mut hardened = AnswerInput::new("where are you from", "china");
+    hardened.salt = Some(salt);
+    let legacy = AnswerInput::new("What's your favorite number", "666");
+
+    let input = RecoverAggregateInput::new(&[(&source, &hardened), (&source, &legacy)], "bob.test.near").unwrap();
+    assert_eq!(serde_json::to_value(&input).unwrap(), circuit_input("recover_aggregate_input.json"));
+    let signals = input.public_signals();
+    assert_eq!(signals.len(), 6);
+    assert_eq!(signals[1], hash1(U256::from_big_endian(b"666")));
+    assert_eq!(signals[2..], [input.root, QuestionCodec::encode("where are you from"), QuestionCodec::encode("What's your favorite number"), input.new_owner]);
+
+    let repeated = RecoverAggregateInput::new(&[(&source, &legacy), (&source, &legacy)], "bob.test.near");
+    assert_eq!(repeated.unwrap_err().0, "Questions must be distinct");
+    let other_root = PathSource::from_state_json(&state_dump(&sample_contract(&QUESTIONS, None))).unwrap();
+    let mixed = RecoverAggregateInput::new(&[(&source, &hardened), (&other_root, &legacy)], "bob.test.near");
+    assert_eq!(mixed.unwrap_err().0, "Paths have different roots");
+    assert!(RecoverAggregateInput::new(&[], "bob.test.near").is_err());
+}
@@ -0,0 +1,1159 @@
+//! Contract methods in the mocked NEAR environment, with proofs generated natively by the prover.
+//! Runs with plain `cargo test`, no sandbox or circom build needed.
+
+use std::sync::OnceLock;
+
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::ProvingKey;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use near_sdk::json_types::U64;
+use near_sdk::test_utils::{accounts, VMContextBuilder};
+use near_sdk::{testing_env, AccountId};
+use near_zk_demo1::*;
+use near_zk_prover::*;
+
+// Tree of 3 layers, 4 leaves
+const DEPTH: usize = 3;
+
+const QUESTIONS: [(&str, &str); 3] = [
+    ("What's your favorite food", "ice cream"),
+    ("where are you from", "china"),
+    ("What's your favorite number", "666"),
+];
+
+struct Keys {
+    update: ProvingKey<Bn254>,
+    recover: ProvingKey<Bn254>,
+    /// Of two answers
+    recover_aggregate: ProvingKey<Bn254>,
+}
+
+/// Keys are set up once for all tests, that is the slow part
+fn keys() -> &'static Keys {
+    static KEYS: OnceLock<Keys> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let mut rng = StdRng::seed_from_u64(0);
+        Keys {
+            update: setup(UpdateCircuit { levels: DEPTH - 1, max_rounds: 0, input: None }, &mut rng).unwrap(),
+            recover: setup(RecoverCircuit { levels: DEPTH - 1, max_rounds: 0, input: None }, &mut rng).unwrap(),
+            recover_aggregate: setup(aggregate_circuit(None), &mut rng).unwrap(),
+        }
+    })
+}
+
+fn vk_json(proving_key: &ProvingKey<Bn254>) -> String {
+    snarkjs_json(&CircomVerificationKey::from(proving_key.vk.clone()))
+}
+
+fn set_context(predecessor: AccountId, timestamp: u64) {
+    testing_env!(VMContextBuilder::new().predecessor_account_id(predecessor).block_timestamp(timestamp).build());
+}
+
+fn leaf(question: &str, answer: &str) -> String {
+    AnswerInput::new(question, answer).witness(None).unwrap().leaf().unwrap().to_string()
+}
+
+fn question(text: &str) -> String {
+    QuestionCodec::encode(text).to_string()
+}
+
+/// Contract owned by alice, applying owner changes immediately
+fn new_contract() -> Contract {
+    set_context(accounts(0), 0);
+    Contract::new(DEPTH, vk_json(&keys().update), vk_json(&keys().recover), None, Some(U64(0)))
+}
+
+/// Contract owned by alice with all of `QUESTIONS`
+fn contract_with_questions() -> Contract {
+    let mut contract = new_contract();
+    for (text, answer) in QUESTIONS {
+        contract.add_security_question(question(text), leaf(text, answer), Some(text.to_string()), None, None, None);
+    }
+    contract
+}
+
+fn path_source(contract: &Contract, text: &str) -> PathSource {
+    let (root, path_indices, siblings) = contract.get_proof_path(question(text)).unwrap();
+    PathSource::from_proof_path(&root, &path_indices, &siblings).unwrap()
+}
+
+/// Recover proof and public signals for the current root
+fn recover_proof(contract: &Contract, text: &str, answer: &str, new_owner: &AccountId) -> (String, String) {
+    let input = RecoverInput::new(&path_source(contract, text), &AnswerInput::new(text, answer), new_owner.as_str()).unwrap();
+    let proof = prove(RecoverCircuit { levels: DEPTH - 1, max_rounds: 0, input: Some(input.clone()) }, &keys().recover, &mut StdRng::seed_from_u64(1)).unwrap();
+    let public = input.public_signals().iter().map(U256::to_string).collect::<Vec<_>>();
+    (snarkjs_json(&CircomProof::from(proof)), snarkjs_json(&public))
+}
+
+fn aggregate_circuit(input: Option<RecoverAggregateInput>) -> RecoverAggregateCircuit {
+    RecoverAggregateCircuit { levels: DEPTH - 1, count: 2, max_rounds: 0, input }
+}
+
+/// Aggregate recover proof and public signals of two answers at the current root
+fn recover_aggregate_proof(contract: &Contract, answers: [(&str, &str); 2], new_owner: &AccountId) -> (String, String) {
+    let sources = answers.map(|(text, _)| path_source(contract, text));
+    let answers = answers.map(|(text, answer)| AnswerInput::new(text, answer));
+    let input = RecoverAggregateInput::new(&[(&sources[0], &answers[0]), (&sources[1], &answers[1])], new_owner.as_str()).unwrap();
+    let proof = prove(aggregate_circuit(Some(input.clone())), &keys().recover_aggregate, &mut StdRng::seed_from_u64(1)).unwrap();
+    let public = input.public_signals().iter().map(U256::to_string).collect::<Vec<_>>();
+    (snarkjs_json(&CircomProof::from(proof)), snarkjs_json(&public))
+}
+
+/// Update proof and public signals replacing `old` with `new` at the current root
+fn update_proof(contract: &Contract, old: (&str, &str), new: (&str, &str)) -> (String, String) {
+    let (old, new) = (AnswerInput::new(old.0, old.1), AnswerInput::new(new.0, new.1));
+    let input = UpdateInput::new(&path_source(contract, &old.question), &old, &new).unwrap();
+    let proof = prove(UpdateCircuit { levels: DEPTH - 1, max_rounds: 0, input: Some(input.clone()) }, &keys().update, &mut StdRng::seed_from_u64(1)).unwrap();
+    let public = input.public_signals().iter().map(U256::to_string).collect::<Vec<_>>();
+    (snarkjs_json(&CircomProof::from(proof)), snarkjs_json(&public))
+}
+
+/// The recover relation before the question became a public signal, `[nullifier, root, new_owner]`
+struct LegacyRecoverCircuit(RecoverInput);
+
+impl ConstraintSynthesizer<Fr> for LegacyRecoverCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let input = self.0;
+        let nullifier = Num::input(&cs, Some(input.nullifier()))?;
+        let root = Num::input(&cs, Some(input.root))?;
+        Num::input(&cs, Some(input.new_owner))?;
+
+        let indices = input.path.path_index().into_iter().map(|v| Num::witness(&cs, Some(v))).collect::<Result<Vec<_>, _>>()?;
+        let siblings = input.path.0.iter().map(|branch| match branch {
+            Branch::Left(v) | Branch::Right(v) => Num::witness(&cs, Some(*v)),
+        });
+        let siblings = siblings.collect::<Result<Vec<_>, _>>()?;
+        let question = Num::witness(&cs, Some(input.question))?;
+        let answer = Num::witness(&cs, Some(input.answer))?;
+
+        let leaf = poseidon_gadget(&cs, &[question, answer.clone()])?;
+        merkle_root_gadget(&cs, leaf, &indices, &siblings)?.enforce_equal(&cs, &root)?;
+        poseidon_gadget(&cs, &[answer])?.enforce_equal(&cs, &nullifier)
+    }
+}
+
+#[test]
+fn test_new() {
+    let contract = new_contract();
+    assert_eq!(contract.get_owner(), accounts(0));
+    assert!(contract.get_questions().is_empty());
+    assert!(contract.get_recovers().is_empty());
+    assert_eq!(contract.get_tree_mode(), TreeMode::Legacy);
+    assert_eq!(contract.get_secondary_root(), None);
+    assert_eq!(contract.get_replace_cooldown(), U64(DEFAULT_REPLACE_COOLDOWN));
+    assert_eq!(contract.get_last_replaced_at(), U64(0));
+    assert_eq!(contract.get_queue_delay(), U64(0));
+
+    let contract = Contract::new(DEPTH, String::new(), String::new(), Some(ByteHashKind::Keccak256), None);
+    assert_eq!(contract.get_tree_mode(), TreeMode::Legacy);
+    assert!(contract.get_secondary_root().is_some());
+    assert_eq!(contract.get_queue_delay(), U64(DEFAULT_QUEUE_DELAY));
+}
+
+#[test]
+fn test_new_queues_by_default() {
+    set_context(accounts(0), 0);
+    let mut contract = Contract::new(DEPTH, vk_json(&keys().update), vk_json(&keys().recover), None, None);
+    let id = contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None).unwrap();
+    assert!(contract.get_questions().is_empty());
+    assert_eq!(contract.get_queued_operation(id).unwrap().execute_after, U64(DEFAULT_QUEUE_DELAY));
+    assert_eq!(contract.set_guardian(accounts(2), None), Some(U64(1)));
+    assert!(contract.get_guardians().is_empty());
+
+    set_context(accounts(0), DEFAULT_QUEUE_DELAY);
+    contract.execute_queued(id);
+    assert_eq!(contract.get_questions(), [question("q")]);
+}
+
+#[test]
+fn test_secondary_commitment_host_functions() {
+    // The contract hashes through the host functions, with the same digests as the `sha3` and `sha2` crates
+    let zero = H256::default();
+    assert_eq!(
+        Keccak256Hash::hash_node(&zero, &zero).to_hex(),
+        "0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"
+    );
+    assert_eq!(
+        Sha256Hash::hash_node(&zero, &zero).to_hex(),
+        "0xf5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"
+    );
+}
+
+#[test]
+fn test_add_security_question() {
+    let contract = contract_with_questions();
+    assert_eq!(contract.get_questions(), QUESTIONS.map(|(text, _)| question(text)));
+    assert_eq!(contract.get_question_texts(), QUESTIONS.map(|(text, _)| Some(text.to_string())));
+    assert_eq!(contract.get_question_text(question("where are you from")), Some("where are you from".to_string()));
+    assert_eq!(contract.get_question_text(question("unknown")), None);
+    assert_eq!(contract.get_question_records()[1].scheme, LeafScheme::Legacy);
+
+    let (root, path_indices, siblings) = contract.get_proof_path(question("where are you from")).unwrap();
+    assert_eq!(path_indices, ["1", "0"]);
+    assert_eq!(siblings[0], leaf("What's your favorite food", "ice cream"));
+    assert_eq!(root, contract.tree.root().to_string());
+    assert_eq!(contract.get_proof_path(question("unknown")), None);
+}
+
+#[test]
+#[should_panic(expected = "Not onwer")]
+fn test_add_security_question_not_owner() {
+    let mut contract = new_contract();
+    set_context(accounts(1), 0);
+    contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Questions exceeds upper limit")]
+fn test_add_security_question_exceeds_capacity() {
+    let mut contract = contract_with_questions();
+    contract.add_security_question(question("q4"), leaf("q4", "a"), None, None, None, None);
+    assert_eq!(contract.get_questions().len(), 4);
+    contract.add_security_question(question("q5"), leaf("q5", "a"), None, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Question exists")]
+fn test_add_security_question_exists() {
+    let mut contract = new_contract();
+    contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None);
+    contract.add_security_question(question("q"), leaf("q", "b"), None, None, None, None);
+}
+
+#[test]
+fn test_remove_security_question() {
+    let mut contract = contract_with_questions();
+    let root = contract.tree.root();
+    contract.remove_security_question(question("where are you from"), false);
+    assert_eq!(contract.questions[1], None);
+    assert_ne!(contract.tree.root(), root);
+
+    // Freed slot is reused and the tree is back to the same leaves
+    contract.add_security_question(question("where are you from"), leaf("where are you from", "china"), None, None, None, None);
+    assert_eq!(contract.tree.root(), root);
+
+    contract.remove_security_question(question("What's your favorite food"), true);
+    assert_eq!(
+        contract.get_questions(),
+        [question("What's your favorite number"), question("where are you from")]
+    );
+    assert_eq!(contract.questions.len(), 2);
+}
+
+#[test]
+fn test_remove_last_security_question() {
+    let mut contract = contract_with_questions();
+    contract.remove_security_question(question("What's your favorite number"), true);
+    assert_eq!(contract.questions.len(), 2);
+    assert_eq!(contract.tree.leaves()[2], U256::zero());
+
+    // The trailing empty slots go with the last question
+    contract.remove_security_question(question("What's your favorite food"), false);
+    contract.remove_security_question(question("where are you from"), false);
+    assert!(contract.questions.is_empty());
+    assert_eq!(contract.tree.root(), new_contract().tree.root());
+}
+
+#[test]
+#[should_panic(expected = "Not onwer")]
+fn test_remove_security_question_not_owner() {
+    let mut contract = contract_with_questions();
+    set_context(accounts(1), 0);
+    contract.remove_security_question(question("where are you from"), false);
+}
+
+#[test]
+#[should_panic(expected = "Question not found")]
+fn test_remove_security_question_not_found() {
+    let mut contract = contract_with_questions();
+    contract.remove_security_question(question("unknown"), false);
+}
+
+#[test]
+fn test_recover_after_remove_security_question() {
+    let mut contract = contract_with_questions();
+    contract.remove_security_question(question("What's your favorite food"), true);
+    assert_eq!(contract.required_answer_weight(), 2);
+
+    // The moved question proves against its new leaf index
+    let (root, path_indices, _) = contract.get_proof_path(question("What's your favorite number")).unwrap();
+    assert_eq!(path_indices, ["0", "0"]);
+    assert_eq!(root, contract.tree.root().to_string());
+    let proofs = QUESTIONS[1..].iter().map(|(text, answer)| recover_proof(&contract, text, answer, &accounts(1))).collect::<Vec<_>>();
+    contract.recover(proofs[0].0.clone(), proofs[0].1.clone());
+    assert_eq!(contract.get_owner(), accounts(0));
+    contract.recover(proofs[1].0.clone(), proofs[1].1.clone());
+    assert_eq!(contract.get_owner(), accounts(1));
+}
+
+#[test]
+#[should_panic(expected = "Invalid proof: question")]
+fn test_recover_removed_security_question() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[0].0, QUESTIONS[0].1, &accounts(1));
+    contract.remove_security_question(question("What's your favorite food"), false);
+    contract.recover(proof, public);
+}
+
+#[test]
+fn test_replace_security_question() {
+    let mut contract = contract_with_questions();
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(1, question("new"), leaf("new", "answer"), None, None, None);
+    assert_eq!(contract.get_questions()[1], question("new"));
+    assert_eq!(contract.get_last_replaced_at(), U64(DEFAULT_REPLACE_COOLDOWN));
+
+    set_context(accounts(0), 2 * DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(2, question("newer"), leaf("newer", "answer"), None, None, None);
+    assert_eq!(contract.get_questions()[2], question("newer"));
+
+    // Same question with a new answer
+    set_context(accounts(0), 3 * DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(2, question("newer"), leaf("newer", "other"), None, None, None);
+    assert_eq!(contract.tree.leaves()[2], U256::from_dec_str(&leaf("newer", "other")).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Question exists")]
+fn test_replace_security_question_exists() {
+    let mut contract = contract_with_questions();
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(1, question(QUESTIONS[0].0), leaf(QUESTIONS[0].0, "answer"), None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "In cooldown")]
+fn test_replace_security_question_in_cooldown() {
+    let mut contract = contract_with_questions();
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(1, question("new"), leaf("new", "answer"), None, None, None);
+    set_context(accounts(0), 2 * DEFAULT_REPLACE_COOLDOWN - 1);
+    contract.replace_security_question(2, question("newer"), leaf("newer", "answer"), None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Question not found")]
+fn test_replace_security_question_not_found() {
+    let mut contract = contract_with_questions();
+    contract.replace_security_question(3, question("new"), leaf("new", "answer"), None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Not onwer")]
+fn test_replace_security_question_not_owner() {
+    let mut contract = contract_with_questions();
+    set_context(accounts(1), DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(1, question("new"), leaf("new", "answer"), None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "In recover")]
+fn test_replace_security_question_in_recover() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[0].0, QUESTIONS[0].1, &accounts(1));
+    contract.recover(proof, public);
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(1, question("new"), leaf("new", "answer"), None, None, None);
+}
+
+#[test]
+fn test_recover_after_replace_security_question() {
+    let mut contract = contract_with_questions();
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(1, question("new"), leaf("new", "answer"), None, None, None);
+
+    let answers = [QUESTIONS[0], ("new", "answer"), QUESTIONS[2]];
+    let proofs = answers.map(|(text, answer)| recover_proof(&contract, text, answer, &accounts(1)));
+    for (proof, public) in proofs {
+        contract.recover(proof, public);
+    }
+    assert_eq!(contract.get_owner(), accounts(1));
+}
+
+#[test]
+fn test_set_replace_cooldown() {
+    let mut contract = new_contract();
+    assert_eq!(contract.set_replace_cooldown(U64(2 * DEFAULT_REPLACE_COOLDOWN)), None);
+    assert_eq!(contract.get_replace_cooldown(), U64(2 * DEFAULT_REPLACE_COOLDOWN));
+
+    // Lowering counts as a replacement
+    set_context(accounts(0), 2 * DEFAULT_REPLACE_COOLDOWN);
+    contract.set_replace_cooldown(U64(1));
+    assert_eq!(contract.get_replace_cooldown(), U64(1));
+    assert_eq!(contract.get_last_replaced_at(), U64(2 * DEFAULT_REPLACE_COOLDOWN));
+}
+
+#[test]
+#[should_panic(expected = "In cooldown")]
+fn test_set_replace_cooldown_in_cooldown() {
+    let mut contract = new_contract();
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN - 1);
+    contract.set_replace_cooldown(U64(1));
+}
+
+#[test]
+fn test_queue() {
+    let mut contract = new_contract();
+    assert_eq!(contract.set_queue_delay(U64(100)), None);
+    assert_eq!(contract.get_queue_delay(), U64(100));
+
+    set_context(accounts(0), 10);
+    let id = contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None).unwrap();
+    assert!(contract.get_questions().is_empty());
+    let operation = contract.get_queued_operation(id).unwrap();
+    assert_eq!(operation.execute_after, U64(110));
+    assert_eq!(contract.get_queued_operations(), [operation]);
+
+    set_context(accounts(0), 110);
+    contract.execute_queued(id);
+    assert_eq!(contract.get_questions(), [question("q")]);
+    assert!(contract.get_queued_operations().is_empty());
+
+    // Lowering the delay waits for the current one
+    let id = contract.set_queue_delay(U64(0)).unwrap();
+    assert_eq!(contract.get_queue_delay(), U64(100));
+    contract.cancel_queued(id);
+    assert_eq!(contract.get_queued_operation(id), None);
+}
+
+#[test]
+#[should_panic(expected = "Queued operation not ready")]
+fn test_execute_queued_not_ready() {
+    let mut contract = new_contract();
+    contract.set_queue_delay(U64(100));
+    let id = contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None).unwrap();
+    set_context(accounts(0), 99);
+    contract.execute_queued(id);
+}
+
+#[test]
+fn test_queue_owner_changes() {
+    let mut contract = contract_with_questions();
+    contract.set_queue_delay(U64(100));
+    let ids = [
+        contract.remove_security_question(question("What's your favorite food"), false),
+        contract.set_replace_cooldown(U64(0)),
+        contract.replace_security_question(1, question("new"), leaf("new", "answer"), None, None, None),
+        contract.set_verification_key("update".to_string(), vk_json(&keys().recover)),
+        contract.set_guardian(accounts(2), None),
+        contract.set_recovery_policy(Some(1), 1),
+    ]
+    .map(Option::unwrap);
+    assert_eq!(contract.get_queued_operations().len(), ids.len());
+    assert_eq!(contract.get_questions(), QUESTIONS.map(|(text, _)| question(text)));
+    assert_eq!(contract.get_replace_cooldown(), U64(DEFAULT_REPLACE_COOLDOWN));
+    assert_eq!(contract.update_verification_key, vk_json(&keys().update));
+    assert!(contract.get_guardians().is_empty());
+    assert_eq!(contract.get_recovery_policy(), RecoveryPolicy::default());
+
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN);
+    for id in ids {
+        contract.execute_queued(id);
+    }
+    assert_eq!(contract.questions[0], None);
+    assert_eq!(contract.get_questions(), [question("new"), question(QUESTIONS[2].0)]);
+    assert_eq!(contract.get_replace_cooldown(), U64(0));
+    assert_eq!(contract.update_verification_key, vk_json(&keys().recover));
+    assert_eq!(contract.get_guardians(), [Guardian { account_id: accounts(2), weight: 1 }]);
+    assert_eq!(contract.get_recovery_policy(), RecoveryPolicy { answer_threshold: Some(1), guardian_threshold: 1 });
+    assert!(contract.get_queued_operations().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Queued operation not found")]
+fn test_execute_queued_not_found() {
+    let mut contract = new_contract();
+    contract.execute_queued(U64(0));
+}
+
+#[test]
+#[should_panic(expected = "Not onwer")]
+fn test_execute_queued_not_owner() {
+    let mut contract = new_contract();
+    contract.set_queue_delay(U64(100));
+    let id = contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None).unwrap();
+    set_context(accounts(1), 100);
+    contract.execute_queued(id);
+}
+
+#[test]
+#[should_panic(expected = "Not owner or recovering account")]
+fn test_cancel_queued_not_owner() {
+    let mut contract = new_contract();
+    contract.set_queue_delay(U64(100));
+    let id = contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None).unwrap();
+    set_context(accounts(1), 0);
+    contract.cancel_queued(id);
+}
+
+#[test]
+fn test_set_verification_key() {
+    let mut contract = new_contract();
+    contract.set_verification_key("update".to_string(), vk_json(&keys().recover));
+    assert_eq!(contract.update_verification_key, vk_json(&keys().recover));
+    contract.set_verification_key("recover_aggregate".to_string(), vk_json(&keys().recover_aggregate));
+    assert_eq!(contract.recover_aggregate_verification_key, vk_json(&keys().recover_aggregate));
+}
+
+#[test]
+#[should_panic(expected = "proof_type error")]
+fn test_set_verification_key_proof_type() {
+    let mut contract = new_contract();
+    contract.set_verification_key("other".to_string(), vk_json(&keys().update));
+}
+
+#[test]
+#[should_panic(expected = "Invalid verification key")]
+fn test_set_verification_key_invalid() {
+    let mut contract = new_contract();
+    contract.set_verification_key("update".to_string(), "{}".to_string());
+}
+
+#[test]
+fn test_update_security_question() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = update_proof(&contract, QUESTIONS[0], ("which season do you like", "autumn"));
+    contract.verify(proof.clone(), public.clone(), "update".to_string());
+    contract.update_security_question(proof, public, Some("which season do you like".to_string()), None, None);
+    assert_eq!(contract.get_questions()[0], question("which season do you like"));
+    assert_eq!(contract.tree.leaves()[0], U256::from_dec_str(&leaf("which season do you like", "autumn")).unwrap());
+    assert_eq!(contract.get_question_texts()[0], Some("which season do you like".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Question exists")]
+fn test_update_security_question_exists() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = update_proof(&contract, QUESTIONS[0], QUESTIONS[1]);
+    contract.update_security_question(proof, public, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Invalid proof: old root")]
+fn test_update_security_question_bad_root() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = update_proof(&contract, QUESTIONS[0], ("which season do you like", "autumn"));
+    contract.remove_security_question(question("What's your favorite number"), false);
+    contract.update_security_question(proof, public, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "In recover")]
+fn test_update_security_question_in_recover() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = update_proof(&contract, QUESTIONS[0], ("which season do you like", "autumn"));
+    let (recover, recover_public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(recover, recover_public);
+    contract.update_security_question(proof, public, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: verified")]
+fn test_update_security_question_wrong_key() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = update_proof(&contract, QUESTIONS[0], ("which season do you like", "autumn"));
+    contract.update_verification_key = vk_json(&setup(UpdateCircuit { levels: DEPTH - 1, max_rounds: 0, input: None }, &mut StdRng::seed_from_u64(2)).unwrap());
+    contract.update_security_question(proof, public, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "proof_type error")]
+fn test_verify_proof_type() {
+    let contract = new_contract();
+    contract.verify(String::new(), String::new(), "other".to_string());
+}
+
+#[test]
+fn test_recover() {
+    let mut contract = contract_with_questions();
+    let new_owner = accounts(1);
+    contract.set_queue_delay(U64(100));
+    let queued = contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None).unwrap();
+
+    let proofs = QUESTIONS.map(|(text, answer)| recover_proof(&contract, text, answer, &new_owner));
+    let (proof, public) = proofs[0].clone();
+    set_context(accounts(2), 0);
+    contract.recover(proof, public.clone());
+    assert_eq!(contract.get_recovers(), [near_sdk::serde_json::from_str::<Vec<String>>(&public).unwrap()[0].clone()]);
+    assert_eq!(contract.get_recovered_weight(), 1);
+    assert_eq!(contract.new_owner, Some(new_owner.clone()));
+    assert_eq!(contract.get_owner(), accounts(0));
+
+    // The recovering account can cancel the owner's queued changes
+    set_context(new_owner.clone(), 0);
+    contract.cancel_queued(queued);
+
+    for (proof, public) in proofs[1..].iter().cloned() {
+        contract.recover(proof, public);
+    }
+    assert_eq!(contract.get_owner(), new_owner);
+    assert_eq!(contract.new_owner, None);
+    assert!(contract.get_questions().is_empty());
+    assert!(contract.get_recovers().is_empty());
+    assert_eq!(contract.get_recovered_weight(), 0);
+    assert!(contract.get_queued_operations().is_empty());
+    assert_eq!(contract.tree.root(), new_contract().tree.root());
+}
+
+#[test]
+#[should_panic(expected = "Repeat recover")]
+fn test_recover_repeated_nullifier() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof.clone(), public.clone());
+    contract.recover(proof, public);
+}
+
+/// `value + p`, the same field element as `value`
+fn plus_modulus(value: &str) -> String {
+    let modulus = U256::from_dec_str("21888242871839275222246405745257275088548364400416034343698204186575808495617").unwrap();
+    (U256::from_dec_str(value).unwrap() + modulus).to_string()
+}
+
+#[test]
+#[should_panic(expected = "Invalid public input")]
+fn test_recover_nullifier_beyond_field() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof.clone(), public.clone());
+
+    // The verifier would reduce the nullifier and accept the proof again
+    let mut signals: Vec<String> = near_sdk::serde_json::from_str(&public).unwrap();
+    signals[0] = plus_modulus(&signals[0]);
+    contract.recover(proof, near_sdk::serde_json::to_string(&signals).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Invalid public input")]
+fn test_recover_without_question_signal_beyond_field() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = legacy_recover_proof(&mut contract, QUESTIONS[1].0, QUESTIONS[1].1);
+    contract.recover(proof.clone(), public.clone());
+
+    let mut signals: Vec<String> = near_sdk::serde_json::from_str(&public).unwrap();
+    signals[0] = plus_modulus(&signals[0]);
+    contract.recover(proof, near_sdk::serde_json::to_string(&signals).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Repeat recover")]
+fn test_recover_nullifier_leading_zeros() {
+    // Another spelling of the same nullifier is a repeat
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof.clone(), public.clone());
+    let mut signals: Vec<String> = near_sdk::serde_json::from_str(&public).unwrap();
+    signals[0] = format!("00{}", signals[0]);
+    contract.recover(proof, near_sdk::serde_json::to_string(&signals).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Invalid new_owner_account")]
+fn test_recover_other_new_owner() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof, public);
+    let (proof, public) = recover_proof(&contract, QUESTIONS[2].0, QUESTIONS[2].1, &accounts(2));
+    contract.recover(proof, public);
+}
+
+#[test]
+#[should_panic(expected = "Invalid proof: root")]
+fn test_recover_bad_root() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None);
+    contract.recover(proof, public);
+}
+
+#[test]
+#[should_panic(expected = "In recover")]
+fn test_remove_security_question_in_recover() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof, public);
+    contract.remove_security_question(question("What's your favorite food"), false);
+}
+
+/// Contract with `QUESTIONS`, guardians charlie of weight 2 and danny of weight 1, and the given policy
+fn contract_with_guardians(answer_threshold: Option<u32>, guardian_threshold: u32) -> Contract {
+    let mut contract = contract_with_questions();
+    contract.set_guardian(accounts(2), Some(2));
+    contract.set_guardian(accounts(3), None);
+    contract.set_recovery_policy(answer_threshold, guardian_threshold);
+    contract
+}
+
+fn approve(contract: &mut Contract, guardian: AccountId, new_owner: AccountId) {
+    set_context(guardian, 0);
+    contract.approve_recovery(new_owner);
+}
+
+#[test]
+fn test_guardians() {
+    let mut contract = contract_with_guardians(Some(2), 1);
+    assert_eq!(contract.get_guardians(), [Guardian { account_id: accounts(2), weight: 2 }, Guardian { account_id: accounts(3), weight: 1 }]);
+    assert_eq!(contract.get_recovery_policy(), RecoveryPolicy { answer_threshold: Some(2), guardian_threshold: 1 });
+    assert_eq!(contract.required_answer_weight(), 2);
+
+    contract.set_guardian(accounts(3), Some(3));
+    assert_eq!(contract.get_guardians()[1].weight, 3);
+    contract.remove_guardian(accounts(2));
+    assert_eq!(contract.get_guardians(), [Guardian { account_id: accounts(3), weight: 3 }]);
+
+    // Queued like other owner changes
+    contract.set_queue_delay(U64(100));
+    let id = contract.set_guardian(accounts(4), None).unwrap();
+    assert_eq!(contract.get_guardians().len(), 1);
+    set_context(accounts(0), 100);
+    contract.execute_queued(id);
+    assert_eq!(contract.get_guardians().len(), 2);
+}
+
+#[test]
+fn test_recover_with_guardians() {
+    let mut contract = contract_with_guardians(Some(2), 1);
+    let new_owner = accounts(1);
+    let proofs = QUESTIONS.map(|(text, answer)| recover_proof(&contract, text, answer, &new_owner));
+
+    // Two answers alone are not enough
+    contract.recover(proofs[0].0.clone(), proofs[0].1.clone());
+    contract.recover(proofs[1].0.clone(), proofs[1].1.clone());
+    assert_eq!(contract.get_owner(), accounts(0));
+    assert_eq!(contract.get_new_owner(), Some(new_owner.clone()));
+
+    approve(&mut contract, accounts(3), new_owner.clone());
+    assert_eq!(contract.get_owner(), new_owner);
+    assert!(contract.get_recovery_approvals().is_empty());
+    assert_eq!(contract.get_approved_weight(new_owner), 0);
+    assert!(contract.get_questions().is_empty());
+    // Guardians and policy stay with the account
+    assert_eq!(contract.get_guardians().len(), 2);
+}
+
+#[test]
+fn test_recover_by_guardians_only() {
+    let mut contract = contract_with_guardians(Some(0), 3);
+    approve(&mut contract, accounts(2), accounts(1));
+    assert_eq!(contract.get_recovery_approvals(), [Approval { guardian: accounts(2), new_owner: accounts(1) }]);
+    assert_eq!(contract.get_approved_weight(accounts(1)), 2);
+    assert_eq!(contract.get_owner(), accounts(0));
+
+    // A removed guardian's approval no longer counts
+    set_context(accounts(0), 0);
+    contract.remove_guardian(accounts(2));
+    assert_eq!(contract.get_approved_weight(accounts(1)), 0);
+    contract.set_guardian(accounts(2), Some(2));
+    approve(&mut contract, accounts(3), accounts(1));
+    assert_eq!(contract.get_owner(), accounts(0));
+    approve(&mut contract, accounts(2), accounts(1));
+    assert_eq!(contract.get_owner(), accounts(1));
+}
+
+#[test]
+fn test_approval_below_threshold_does_not_lock_new_owner() {
+    let mut contract = contract_with_guardians(Some(2), 3);
+    contract.set_queue_delay(U64(100));
+    let queued = contract.set_guardian(accounts(4), None).unwrap();
+    approve(&mut contract, accounts(3), accounts(4));
+    assert_eq!(contract.get_new_owner(), None);
+    assert_eq!(contract.get_approved_weight(accounts(4)), 1);
+
+    // Recover proofs to another account go ahead, and the guardian can change its approval
+    let proofs = QUESTIONS.map(|(text, answer)| recover_proof(&contract, text, answer, &accounts(1)));
+    contract.recover(proofs[0].0.clone(), proofs[0].1.clone());
+    assert_eq!(contract.get_new_owner(), Some(accounts(1)));
+    approve(&mut contract, accounts(2), accounts(1));
+    approve(&mut contract, accounts(3), accounts(1));
+    assert_eq!(contract.get_approved_weight(accounts(4)), 0);
+    assert_eq!(contract.get_recovery_approvals().len(), 2);
+    contract.recover(proofs[1].0.clone(), proofs[1].1.clone());
+    assert_eq!(contract.get_owner(), accounts(1));
+    assert_eq!(contract.get_queued_operation(queued), None);
+}
+
+#[test]
+#[should_panic(expected = "Not owner or recovering account")]
+fn test_approved_account_cannot_cancel_queued() {
+    let mut contract = contract_with_guardians(Some(2), 3);
+    contract.set_queue_delay(U64(100));
+    let queued = contract.set_guardian(accounts(4), None).unwrap();
+    approve(&mut contract, accounts(2), accounts(4));
+    set_context(accounts(4), 0);
+    contract.cancel_queued(queued);
+}
+
+#[test]
+fn test_guardians_reaching_threshold_start_recovery() {
+    let mut contract = contract_with_guardians(Some(2), 2);
+    approve(&mut contract, accounts(2), accounts(1));
+    assert_eq!(contract.get_new_owner(), Some(accounts(1)));
+    assert_eq!(contract.get_owner(), accounts(0));
+}
+
+#[test]
+fn test_cancel_recovery() {
+    let mut contract = contract_with_guardians(Some(2), 1);
+    let (proof, public) = recover_proof(&contract, QUESTIONS[0].0, QUESTIONS[0].1, &accounts(1));
+    contract.recover(proof.clone(), public.clone());
+    approve(&mut contract, accounts(3), accounts(1));
+    assert_eq!(contract.get_recovered_weight(), 1);
+
+    set_context(accounts(2), 0);
+    contract.cancel_recovery();
+    assert_eq!(contract.get_new_owner(), None);
+    assert!(contract.get_recovers().is_empty());
+    assert!(contract.recovered_questions.is_empty());
+    assert_eq!(contract.get_recovered_weight(), 0);
+    assert!(contract.get_recovery_approvals().is_empty());
+
+    // A recovery to another account can start
+    let proofs = QUESTIONS.map(|(text, answer)| recover_proof(&contract, text, answer, &accounts(4)));
+    contract.recover(proofs[0].0.clone(), proofs[0].1.clone());
+    contract.recover(proofs[1].0.clone(), proofs[1].1.clone());
+    approve(&mut contract, accounts(3), accounts(4));
+    assert_eq!(contract.get_owner(), accounts(4));
+}
+
+#[test]
+fn test_cancel_recovery_by_new_owner() {
+    let mut contract = contract_with_guardians(Some(2), 1);
+    approve(&mut contract, accounts(3), accounts(1));
+    set_context(accounts(1), 0);
+    contract.cancel_recovery();
+    assert_eq!(contract.get_new_owner(), None);
+    assert!(contract.get_recovery_approvals().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Not guardian or recovering account")]
+fn test_cancel_recovery_by_owner() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_proof(&contract, QUESTIONS[0].0, QUESTIONS[0].1, &accounts(1));
+    contract.recover(proof, public);
+    set_context(accounts(0), 0);
+    contract.cancel_recovery();
+}
+
+#[test]
+#[should_panic(expected = "Not guardian or recovering account")]
+fn test_cancel_recovery_by_other_account() {
+    let mut contract = contract_with_guardians(Some(2), 1);
+    approve(&mut contract, accounts(3), accounts(1));
+    set_context(accounts(4), 0);
+    contract.cancel_recovery();
+}
+
+#[test]
+#[should_panic(expected = "In recover")]
+fn test_execute_queued_in_recover() {
+    let mut contract = contract_with_guardians(Some(2), 3);
+    contract.set_queue_delay(U64(100));
+    let queued = contract.set_guardian(accounts(4), None).unwrap();
+    approve(&mut contract, accounts(2), accounts(1));
+    assert_eq!(contract.get_new_owner(), None);
+    set_context(accounts(0), 100);
+    contract.execute_queued(queued);
+}
+
+#[test]
+#[should_panic(expected = "In recover")]
+fn test_remove_security_question_in_guardian_recover() {
+    let mut contract = contract_with_guardians(Some(2), 1);
+    approve(&mut contract, accounts(3), accounts(1));
+    assert!(contract.get_recovers().is_empty());
+    set_context(accounts(0), 0);
+    contract.remove_security_question(question("What's your favorite food"), false);
+}
+
+#[test]
+fn test_default_policy_ignores_guardians() {
+    let mut contract = contract_with_questions();
+    contract.set_guardian(accounts(2), None);
+    approve(&mut contract, accounts(2), accounts(1));
+    assert_eq!(contract.get_owner(), accounts(0));
+    assert_eq!(contract.required_answer_weight(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Not guardian")]
+fn test_approve_recovery_not_guardian() {
+    let mut contract = contract_with_guardians(None, 1);
+    approve(&mut contract, accounts(4), accounts(1));
+}
+
+#[test]
+#[should_panic(expected = "Repeat approval")]
+fn test_approve_recovery_repeated() {
+    let mut contract = contract_with_guardians(None, 3);
+    approve(&mut contract, accounts(2), accounts(1));
+    approve(&mut contract, accounts(2), accounts(1));
+}
+
+#[test]
+#[should_panic(expected = "Invalid new_owner_account")]
+fn test_approve_recovery_other_new_owner() {
+    let mut contract = contract_with_guardians(None, 1);
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof, public);
+    approve(&mut contract, accounts(2), accounts(4));
+}
+
+#[test]
+#[should_panic(expected = "Invalid recovery policy")]
+fn test_set_recovery_policy_empty() {
+    let mut contract = new_contract();
+    contract.set_recovery_policy(Some(0), 0);
+}
+
+#[test]
+#[should_panic(expected = "Invalid guardian weight")]
+fn test_set_guardian_zero_weight() {
+    let mut contract = new_contract();
+    contract.set_guardian(accounts(2), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "Guardian not found")]
+fn test_remove_guardian_not_found() {
+    let mut contract = new_contract();
+    contract.remove_guardian(accounts(2));
+}
+
+/// Contract owned by alice with `QUESTIONS` of weights 1, 3 and 1
+fn contract_with_weights() -> Contract {
+    let mut contract = new_contract();
+    for ((text, answer), weight) in QUESTIONS.into_iter().zip([1, 3, 1]) {
+        contract.add_security_question(question(text), leaf(text, answer), None, None, None, Some(weight));
+    }
+    contract
+}
+
+#[test]
+fn test_recover_with_question_weights() {
+    let mut contract = contract_with_weights();
+    assert_eq!(contract.get_question_records().iter().map(|v| v.weight).collect::<Vec<_>>(), [1, 3, 1]);
+    assert_eq!(contract.required_answer_weight(), 5);
+    contract.set_recovery_policy(Some(4), 0);
+    assert_eq!(contract.required_answer_weight(), 4);
+
+    let proofs = QUESTIONS.map(|(text, answer)| recover_proof(&contract, text, answer, &accounts(1)));
+    contract.recover(proofs[0].0.clone(), proofs[0].1.clone());
+    contract.recover(proofs[2].0.clone(), proofs[2].1.clone());
+    assert_eq!(contract.get_recovered_weight(), 2);
+    assert_eq!(contract.get_owner(), accounts(0));
+
+    contract.recover(proofs[1].0.clone(), proofs[1].1.clone());
+    assert_eq!(contract.get_owner(), accounts(1));
+    assert_eq!(contract.get_recovered_weight(), 0);
+}
+
+#[test]
+fn test_heavy_question_alone() {
+    let mut contract = contract_with_weights();
+    contract.set_recovery_policy(Some(3), 0);
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof, public);
+    assert_eq!(contract.get_owner(), accounts(1));
+}
+
+/// Recover proof of the original circuit without the question signal, setting its key on the contract
+fn legacy_recover_proof(contract: &mut Contract, text: &str, answer: &str) -> (String, String) {
+    let mut rng = StdRng::seed_from_u64(3);
+    let input = RecoverInput::new(&path_source(contract, text), &AnswerInput::new(text, answer), accounts(1).as_str()).unwrap();
+    let proving_key = setup(LegacyRecoverCircuit(input.clone()), &mut rng).unwrap();
+    contract.recover_verification_key = vk_json(&proving_key);
+
+    let proof = prove(LegacyRecoverCircuit(input.clone()), &proving_key, &mut rng).unwrap();
+    let public = [input.nullifier(), input.root, input.new_owner].map(|v| v.to_string());
+    (snarkjs_json(&CircomProof::from(proof)), snarkjs_json(&public))
+}
+
+#[test]
+fn test_recover_without_question_signal() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = legacy_recover_proof(&mut contract, QUESTIONS[1].0, QUESTIONS[1].1);
+    contract.recover(proof, public);
+    assert_eq!(contract.get_recovered_weight(), 1);
+    assert!(contract.recovered_questions.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Weighted questions need the question signal")]
+fn test_recover_without_question_signal_weighted() {
+    // Proof of the heaviest question, but the contract cannot tell which one it is
+    let mut contract = contract_with_weights();
+    let (proof, public) = legacy_recover_proof(&mut contract, QUESTIONS[1].0, QUESTIONS[1].1);
+    contract.recover(proof, public);
+}
+
+#[test]
+fn test_weight_kept_on_replace_and_update() {
+    let mut contract = contract_with_weights();
+    let (proof, public) = update_proof(&contract, QUESTIONS[1], ("which season do you like", "autumn"));
+    contract.update_security_question(proof, public, None, None, None);
+    assert_eq!(contract.get_question_records()[1].weight, 3);
+
+    set_context(accounts(0), DEFAULT_REPLACE_COOLDOWN);
+    contract.replace_security_question(1, question("new"), leaf("new", "answer"), None, None, None);
+    assert_eq!(contract.get_question_records()[1].weight, 3);
+}
+
+#[test]
+#[should_panic(expected = "Invalid question weight")]
+fn test_add_security_question_zero_weight() {
+    let mut contract = new_contract();
+    contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, Some(0));
+}
+
+#[test]
+#[should_panic(expected = "Invalid public input")]
+fn test_recover_malformed_public_input() {
+    let mut contract = contract_with_questions();
+    contract.recover(String::new(), "[\"1\", \"2\"]".to_string());
+}
+
+fn enable_aggregate(contract: &mut Contract) {
+    set_context(accounts(0), 0);
+    contract.set_verification_key("recover_aggregate".to_string(), vk_json(&keys().recover_aggregate));
+}
+
+#[test]
+fn test_recover_aggregate() {
+    let mut contract = contract_with_questions();
+    enable_aggregate(&mut contract);
+    contract.set_recovery_policy(Some(2), 0);
+    let (proof, public) = recover_aggregate_proof(&contract, [QUESTIONS[0], QUESTIONS[2]], &accounts(1));
+    set_context(accounts(2), 0);
+    contract.recover_aggregate(proof, public);
+    assert_eq!(contract.get_owner(), accounts(1));
+    assert!(contract.get_questions().is_empty());
+    assert!(contract.get_recovers().is_empty());
+}
+
+#[test]
+fn test_recover_aggregate_with_single_proofs() {
+    let mut contract = contract_with_weights();
+    enable_aggregate(&mut contract);
+    let (proof, public) = recover_aggregate_proof(&contract, [QUESTIONS[1], QUESTIONS[2]], &accounts(1));
+    let (single, single_public) = recover_proof(&contract, QUESTIONS[0].0, QUESTIONS[0].1, &accounts(1));
+    contract.recover_aggregate(proof, public.clone());
+    assert_eq!(contract.get_recovered_weight(), 4);
+    assert_eq!(contract.get_recovers(), near_sdk::serde_json::from_str::<Vec<String>>(&public).unwrap()[..2]);
+    assert_eq!(contract.get_owner(), accounts(0));
+
+    contract.recover(single, single_public);
+    assert_eq!(contract.get_owner(), accounts(1));
+}
+
+#[test]
+#[should_panic(expected = "Repeat recover")]
+fn test_recover_aggregate_after_single_proof() {
+    let mut contract = contract_with_questions();
+    enable_aggregate(&mut contract);
+    let (single, single_public) = recover_proof(&contract, QUESTIONS[0].0, QUESTIONS[0].1, &accounts(1));
+    let (proof, public) = recover_aggregate_proof(&contract, [QUESTIONS[0], QUESTIONS[2]], &accounts(1));
+    contract.recover(single, single_public);
+    contract.recover_aggregate(proof, public);
+}
+
+#[test]
+#[should_panic(expected = "Repeat recover")]
+fn test_recover_aggregate_repeated_leaf() {
+    let mut contract = contract_with_questions();
+    enable_aggregate(&mut contract);
+    let input = RecoverAggregateInput::new(&[(&path_source(&contract, QUESTIONS[1].0), &AnswerInput::new(QUESTIONS[1].0, QUESTIONS[1].1))], "bob").unwrap();
+    let repeated = RecoverAggregateInput { paths: [input.paths.clone(), input.paths].concat(), leaves: [input.leaves.clone(), input.leaves].concat(), ..input };
+    let proof = prove(aggregate_circuit(Some(repeated.clone())), &keys().recover_aggregate, &mut StdRng::seed_from_u64(1)).unwrap();
+    let public = repeated.public_signals().iter().map(U256::to_string).collect::<Vec<_>>();
+    contract.recover_aggregate(snarkjs_json(&CircomProof::from(proof)), snarkjs_json(&public));
+}
+
+#[test]
+#[should_panic(expected = "Invalid proof: root")]
+fn test_recover_aggregate_bad_root() {
+    let mut contract = contract_with_questions();
+    enable_aggregate(&mut contract);
+    let (proof, public) = recover_aggregate_proof(&contract, [QUESTIONS[0], QUESTIONS[2]], &accounts(1));
+    contract.add_security_question(question("q"), leaf("q", "a"), None, None, None, None);
+    contract.recover_aggregate(proof, public);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: verified")]
+fn test_recover_aggregate_wrong_key() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_aggregate_proof(&contract, [QUESTIONS[0], QUESTIONS[2]], &accounts(1));
+    let other = setup(aggregate_circuit(None), &mut StdRng::seed_from_u64(2)).unwrap();
+    contract.set_verification_key("recover_aggregate".to_string(), vk_json(&other));
+    contract.recover_aggregate(proof, public);
+}
+
+#[test]
+#[should_panic(expected = "Aggregate recovery not enabled")]
+fn test_recover_aggregate_not_enabled() {
+    let mut contract = contract_with_questions();
+    let (proof, public) = recover_aggregate_proof(&contract, [QUESTIONS[0], QUESTIONS[2]], &accounts(1));
+    contract.recover_aggregate(proof, public);
+}
+
+#[test]
+#[should_panic(expected = "Invalid public input")]
+fn test_recover_aggregate_malformed_public_input() {
+    let mut contract = contract_with_questions();
+    enable_aggregate(&mut contract);
+    contract.recover_aggregate(String::new(), "[\"1\", \"2\", \"3\"]".to_string());
+}
+
+/// State of the first contract version with `QUESTIONS`, owned by alice, at alice's account
+fn write_legacy_state(recovers: Vec<String>) {
+    testing_env!(VMContextBuilder::new().current_account_id(accounts(0)).predecessor_account_id(accounts(0)).build());
+    let leaves = QUESTIONS.map(|(text, answer)| U256::from_dec_str(&leaf(text, answer)).unwrap());
+    let (left, right) = (hash2(leaves[0], leaves[1]), hash2(leaves[2], U256::zero()));
+    let empty = vec![U256::zero(), hash2(U256::zero(), U256::zero()), hash2(hash2(U256::zero(), U256::zero()), hash2(U256::zero(), U256::zero()))];
+    let legacy = LegacyContract {
+        update_verification_key: vk_json(&keys().update),
+        recover_verification_key: vk_json(&keys().recover),
+        owner_id: accounts(0),
+        tree: LegacyMerkleTree {
+            depth: DEPTH,
+            empty,
+            nodes: vec![hash2(left, right), left, right, leaves[0], leaves[1], leaves[2], U256::zero()],
+        },
+        questions: QUESTIONS.map(|(text, _)| question(text)).to_vec(),
+        recovers,
+        new_owner: None,
+    };
+    near_sdk::env::state_write(&legacy);
+}
+
+#[test]
+fn test_migrate() {
+    write_legacy_state(Vec::new());
+    let mut contract = Contract::migrate();
+    assert_eq!(contract.get_owner(), accounts(0));
+    assert_eq!(contract.get_questions(), QUESTIONS.map(|(text, _)| question(text)));
+    assert!(contract.get_question_records().iter().all(|v| v.scheme == LeafScheme::Legacy && v.weight == 1));
+    assert_eq!(contract.get_tree_mode(), TreeMode::Legacy);
+    assert_eq!(contract.get_queue_delay(), U64(DEFAULT_QUEUE_DELAY));
+    assert_eq!(contract.get_replace_cooldown(), U64(DEFAULT_REPLACE_COOLDOWN));
+
+    // Same tree as a contract created with this code, and proofs against it verify
+    let root = contract_with_questions().tree.root();
+    assert_eq!(contract.tree.root(), root);
+    set_context(accounts(1), 0);
+    let (proof, public) = recover_proof(&contract, QUESTIONS[1].0, QUESTIONS[1].1, &accounts(1));
+    contract.recover(proof, public);
+    assert_eq!(contract.recovered_weight, 1);
+    assert_eq!(contract.get_new_owner(), Some(accounts(1)));
+}
+
+#[test]
+fn test_migrate_in_recover() {
+    write_legacy_state(vec!["123".to_string()]);
+    let contract = Contract::migrate();
+    assert_eq!(contract.get_recovers(), ["123"]);
+    assert_eq!(contract.recovered_weight, 1);
+    assert!(contract.recovered_questions.is_empty());
+}
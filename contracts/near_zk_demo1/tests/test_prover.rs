@@ -0,0 +1,200 @@
+use ark_bn254::{Bn254, Fq12, Fr};
+use ark_ec::PairingEngine;
+use ark_ff::One;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use near_sdk::json_types::U64;
+use near_sdk::serde_json;
+use near_zk_demo1::*;
+use near_zk_prover::*;
+
+fn circuit_input<T: near_sdk::serde::de::DeserializeOwned>(name: &str) -> T {
+    let path = format!("{}/../../circuits/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn proof_path(input: &serde_json::Value) -> PathSource {
+    let path = serde_json::json!([input["root"], input["pathIndices"], input["siblings"]]);
+    PathSource::from_proof_path_json(&path.to_string()).unwrap()
+}
+
+fn public_inputs(signals: &[U256]) -> Vec<Fr> {
+    signals.iter().map(|v| Fr::try_from(*v).unwrap()).collect()
+}
+
+fn is_satisfied<C: ConstraintSynthesizer<Fr>>(circuit: C) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    circuit.generate_constraints(cs.clone()).unwrap();
+    cs.is_satisfied().unwrap()
+}
+
+fn recover_input(answer: &str, new_owner: &str) -> RecoverInput {
+    let source = proof_path(&circuit_input("recover_input.json"));
+    let answer = AnswerInput::new("where are you from", answer);
+    RecoverInput::new(&source, &answer, new_owner).unwrap()
+}
+
+#[test]
+fn test_recover_proof() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let input = recover_input("china", "bob.test.near");
+    let circuit = RecoverCircuit { levels: 2, max_rounds: 0, input: Some(input.clone()) };
+    assert!(is_satisfied(circuit.clone()));
+
+    let proving_key = setup(RecoverCircuit { levels: 2, max_rounds: 0, input: None }, &mut rng).unwrap();
+    let proof = prove(circuit, &proving_key, &mut rng).unwrap();
+    let verifying_key = ark_groth16::prepare_verifying_key(&proving_key.vk);
+    let signals = input.public_signals();
+    assert!(ark_groth16::verify_proof(&verifying_key, &proof, &public_inputs(&signals)).unwrap());
+
+    // The proof is bound to the new owner
+    let mut other_owner = signals.clone();
+    other_owner[3] = encode_account_id("carol.test.near").unwrap();
+    assert!(!ark_groth16::verify_proof(&verifying_key, &proof, &public_inputs(&other_owner)).unwrap());
+}
+
+#[test]
+fn test_recover_rejects_wrong_answer() {
+    let mut input = recover_input("china", "bob.test.near");
+    input.answer = U256::from_big_endian(b"japan");
+    assert!(!is_satisfied(RecoverCircuit { levels: 2, max_rounds: 0, input: Some(input) }));
+}
+
+#[test]
+fn test_hardened_recover() {
+    let json: serde_json::Value = circuit_input("recover_hardened_input.json");
+    let mut answer = AnswerInput::new("where are you from", "china");
+    answer.salt = Some(U256::from_dec_str(json["salt"].as_str().unwrap()).unwrap());
+    answer.scheme = Some(LeafScheme::Hardened { rounds: 16 });
+    let input = RecoverInput::new(&proof_path(&json), &answer, "bob.test.near").unwrap();
+
+    assert!(is_satisfied(RecoverCircuit { levels: 2, max_rounds: MAX_HARDENED_ROUNDS, input: Some(input.clone()) }));
+    assert!(!is_satisfied(RecoverCircuit { levels: 2, max_rounds: 0, input: Some(input) }));
+
+    // Legacy leaves are hardened leaves with zero rounds
+    let legacy = recover_input("china", "bob.test.near");
+    let legacy = RecoverInput { salt: Some(U256::zero()), rounds: Some(U256::zero()), ..legacy };
+    assert!(is_satisfied(RecoverCircuit { levels: 2, max_rounds: MAX_HARDENED_ROUNDS, input: Some(legacy) }));
+}
+
+/// Contract with the questions of test_scene.rs after the update, "where are you from" hardened with `salt` if given
+fn aggregate_contract(salt: Option<U256>) -> Contract {
+    let mut contract = Contract::new(3, String::new(), String::new(), None, Some(U64(0)));
+    let questions = [("which season do you like", "autumn"), ("where are you from", "china"), ("What's your favorite number", "666")];
+    for (question, answer) in questions {
+        let scheme = match salt {
+            Some(_) if question == "where are you from" => LeafScheme::Hardened { rounds: MAX_HARDENED_ROUNDS },
+            _ => LeafScheme::Legacy,
+        };
+        let value = QuestionCodec::encode(question);
+        let leaf = scheme.leaf(value, U256::from_big_endian(answer.as_bytes()), salt.unwrap_or_default());
+        contract.add_security_question(value.to_string(), leaf.to_string(), None, Some(scheme), None, None);
+    }
+    contract
+}
+
+fn aggregate_input(contract: &Contract, answers: [AnswerInput; 2]) -> RecoverAggregateInput {
+    let sources = answers.clone().map(|answer| {
+        let (root, path_indices, siblings) = contract.get_proof_path(QuestionCodec::encode(&answer.question).to_string()).unwrap();
+        PathSource::from_proof_path(&root, &path_indices, &siblings).unwrap()
+    });
+    RecoverAggregateInput::new(&[(&sources[0], &answers[0]), (&sources[1], &answers[1])], "bob.test.near").unwrap()
+}
+
+#[test]
+fn test_recover_aggregate_proof() {
+    let mut rng = StdRng::seed_from_u64(4);
+    let answers = [AnswerInput::new("where are you from", "china"), AnswerInput::new("What's your favorite number", "666")];
+    let input = aggregate_input(&aggregate_contract(None), answers.clone());
+    let circuit = |input| RecoverAggregateCircuit { levels: 2, count: 2, max_rounds: 0, input };
+    let proving_key = setup(circuit(None), &mut rng).unwrap();
+    let proof = prove(circuit(Some(input.clone())), &proving_key, &mut rng).unwrap();
+    let verifying_key = ark_groth16::prepare_verifying_key(&proving_key.vk);
+    let signals = input.public_signals();
+    assert!(ark_groth16::verify_proof(&verifying_key, &proof, &public_inputs(&signals)).unwrap());
+
+    // Each nullifier is bound to its question
+    let mut swapped = signals.clone();
+    swapped.swap(0, 1);
+    assert!(!ark_groth16::verify_proof(&verifying_key, &proof, &public_inputs(&swapped)).unwrap());
+
+    let mut wrong = input.clone();
+    wrong.leaves[1].answer = U256::from_big_endian(b"777");
+    assert!(!is_satisfied(circuit(Some(wrong))));
+
+    // Hardened and legacy leaves together
+    let salt = U256::from(12345);
+    let [mut hardened, legacy] = answers;
+    hardened.salt = Some(salt);
+    hardened.scheme = Some(LeafScheme::Hardened { rounds: MAX_HARDENED_ROUNDS });
+    let input = aggregate_input(&aggregate_contract(Some(salt)), [hardened, legacy]);
+    assert!(is_satisfied(RecoverAggregateCircuit { levels: 2, count: 2, max_rounds: MAX_HARDENED_ROUNDS, input: Some(input.clone()) }));
+    assert!(!is_satisfied(RecoverAggregateCircuit { levels: 2, count: 2, max_rounds: 0, input: Some(input) }));
+}
+
+#[test]
+fn test_update_proof() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let json: serde_json::Value = circuit_input("update_input.json");
+    let input = UpdateInput::new(
+        &proof_path(&json),
+        &AnswerInput::new("What's your favorite food", "ice cream"),
+        &AnswerInput::new("which season do you like", "autumn"),
+    )
+    .unwrap();
+
+    let proving_key = setup(UpdateCircuit { levels: 2, max_rounds: 0, input: None }, &mut rng).unwrap();
+    let proof = prove(UpdateCircuit { levels: 2, max_rounds: 0, input: Some(input.clone()) }, &proving_key, &mut rng).unwrap();
+    let verifying_key = ark_groth16::prepare_verifying_key(&proving_key.vk);
+    assert!(ark_groth16::verify_proof(&verifying_key, &proof, &public_inputs(&input.public_signals())).unwrap());
+
+    // Same public signals as the snarkjs proof in tests/data
+    let public: Vec<String> = serde_json::from_str(include_str!("data/update_public.json")).unwrap();
+    assert_eq!(input.public_signals().iter().map(U256::to_string).collect::<Vec<_>>(), public);
+}
+
+#[test]
+fn test_poseidon_gadget() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let inputs = [1u64, 2, 3].map(|v| Num::witness(&cs, Some(U256::from(v))).unwrap());
+    let out = poseidon_gadget(&cs, &inputs).unwrap();
+    assert_eq!(out.value.map(U256::from), Some(poseidon(&[1, 2, 3].map(U256::from))));
+    assert!(cs.is_satisfied().unwrap());
+    // circomlib's Poseidon(3) has 8 full and 56 partial rounds of x^5 S-boxes
+    assert_eq!(cs.num_constraints(), 3 * (8 * 4 + 56));
+}
+
+#[test]
+fn test_arkworks_keys_in_contract() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let input = recover_input("china", "bob.test.near");
+    let proving_key = setup(RecoverCircuit { levels: 2, max_rounds: 0, input: None }, &mut rng).unwrap();
+    let proof = prove(RecoverCircuit { levels: 2, max_rounds: 0, input: Some(input.clone()) }, &proving_key, &mut rng).unwrap();
+
+    let vk = CircomVerificationKey::from(proving_key.vk.clone());
+    assert_eq!(vk.n_public, 4);
+    // e(alpha, beta) of the key, the value snarkjs precomputes
+    let alphabeta = Bn254::pairing(proving_key.vk.alpha_g1, proving_key.vk.beta_g2);
+    assert_ne!(alphabeta, Fq12::one());
+    assert_eq!(vk.vk_alphabeta_12, circom_fq12(&alphabeta));
+    assert_eq!(ark_groth16::VerifyingKey::from(vk.clone()), proving_key.vk);
+    let circom_proof = CircomProof::from(proof.clone());
+    assert_eq!(circom_proof.pi_a[2], "1");
+    assert_eq!(ark_groth16::Proof::from(circom_proof), proof);
+
+    let vk_json = snarkjs_json(&vk);
+    assert!(vk_json.starts_with("{\n \"protocol\": \"groth16\",\n \"curve\": \"bn128\",\n \"nPublic\": 4,\n \"vk_alpha_1\": [\n  \""));
+    assert!(vk_json.ends_with("\n  ]\n ]\n}"));
+
+    // Deployed and submitted unchanged
+    let mut contract = Contract::new(3, vk_json.clone(), vk_json, None, Some(U64(0)));
+    let questions = [("which season do you like", "autumn"), ("where are you from", "china"), ("What's your favorite number", "666")];
+    for (question, answer) in questions {
+        let question = QuestionCodec::encode(question);
+        let leaf = hash2(question, U256::from_big_endian(answer.as_bytes()));
+        contract.add_security_question(question.to_string(), leaf.to_string(), None, None, None, None);
+    }
+    let public = input.public_signals().iter().map(U256::to_string).collect::<Vec<_>>();
+    contract.recover(snarkjs_json(&CircomProof::from(proof)), snarkjs_json(&public));
+    assert_eq!(contract.get_recovers(), [public[0].clone()]);
+}
@@ -16,7 +16,7 @@ async fn test_sence() -> anyhow::Result<()> {
     println!("");
     println!(" ====== add_security_question ====== ");
 
-    let alice_question = U256::from_big_endian("What's your favorite food".as_bytes());
+    let alice_question = QuestionCodec::encode("What's your favorite food");
     let alice_answer = U256::from_big_endian("ice cream".as_bytes());
     let alice_leaf = hash2(alice_question.clone(), alice_answer);
     println!("alice_question: {:?}", alice_question.to_string());
@@ -24,7 +24,7 @@ async fn test_sence() -> anyhow::Result<()> {
     println!("alice_leaf: {:?}", alice_leaf.to_string());
     call_add_security_question(&worker, &near_zk_demo1, &deploy_account, alice_question.to_string(), alice_leaf.to_string()).await?.is_success();
     
-    let bob_question = U256::from_big_endian("where are you from".as_bytes());
+    let bob_question = QuestionCodec::encode("where are you from");
     let bob_answer = U256::from_big_endian("china".as_bytes());
     let bob_leaf = hash2(bob_question.clone(), bob_answer);
     println!("bob_question: {:?}", bob_question.to_string());
@@ -32,7 +32,7 @@ async fn test_sence() -> anyhow::Result<()> {
     println!("bob_leaf: {:?}", bob_leaf.to_string());
     call_add_security_question(&worker, &near_zk_demo1, &deploy_account, bob_question.to_string(), bob_leaf.to_string()).await?.is_success();
     
-    let carol_question = U256::from_big_endian("What's your favorite number".as_bytes());
+    let carol_question = QuestionCodec::encode("What's your favorite number");
     let carol_answer = U256::from_big_endian("666".as_bytes());
     let carol_leaf = hash2(carol_question.clone(), carol_answer);
     println!("carol_question: {:?}", carol_question.to_string());
@@ -46,7 +46,7 @@ async fn test_sence() -> anyhow::Result<()> {
     println!("");
     println!(" ====== update_security_question ====== ");
 
-    let new_alice_question = U256::from_big_endian("which season do you like".as_bytes());
+    let new_alice_question = QuestionCodec::encode("which season do you like");
     let new_alice_answer = U256::from_big_endian("autumn".as_bytes());
     let new_alice_leaf = hash2(new_alice_question.clone(), new_alice_answer);
     println!("new_alice_question: {:?}", new_alice_question.to_string());
@@ -106,10 +106,6 @@ async fn test_sence() -> anyhow::Result<()> {
 
 fn question_to_utf8(questions: Vec<String>) -> Vec<String> {
     questions.into_iter().map(|v| {
-        let mut bytes = [0u8; 32];
-        let v_u256 = U256::from_str_radix(&v, 10).unwrap();
-        v_u256.to_big_endian(&mut bytes);
-        let res = String::from_utf8(bytes.to_vec()).unwrap();
-        res.replace("\0", "")
+        QuestionCodec::decode(U256::from_dec_str(&v).unwrap()).unwrap_or(v)
     }).collect::<Vec<String>>()
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+//! Writes the Poseidon constant tables for 1 to 16 inputs to `$OUT_DIR/poseidon_constants.rs`,
+//! so the contract does not run the Grain LFSR on every call.
+
+#[path = "src/poseidon_grain.rs"]
+mod poseidon_grain;
+
+use std::fmt::Write;
+
+use ark_bn254::Fr;
+
+/// Number of full rounds, for every width
+const ROUNDS_F: usize = 8;
+
+/// Number of partial rounds by number of inputs, starting at one input, as in circomlib
+const ROUNDS_P: [usize; 16] = [56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68];
+
+/// Static array of field elements in Montgomery form, `t` per line
+fn write_table(out: &mut String, name: &str, values: &[Fr], t: usize) {
+    writeln!(out, "static {}: [Fr; {}] = [", name, values.len()).unwrap();
+    for row in values.chunks(t) {
+        let row = row.iter().map(|v| format!("Fr::new(BigInteger256({:?}))", v.0 .0)).collect::<Vec<_>>();
+        writeln!(out, "    {},", row.join(", ")).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/poseidon_grain.rs");
+
+    let mut out = String::from("// Generated by build.rs\n\n");
+    let mut params = String::new();
+    for (i, rounds_p) in ROUNDS_P.into_iter().enumerate() {
+        let (n, t) = (i + 1, i + 2);
+        let (c, m) = poseidon_grain::generate(t, ROUNDS_F, rounds_p);
+        write_table(&mut out, &format!("C{}", n), &c, t);
+        write_table(&mut out, &format!("M{}", n), &m, t);
+        writeln!(
+            params,
+            "    PoseidonParams {{ t: {}, rounds_f: {}, rounds_p: {}, c: Cow::Borrowed(&C{}), m: Cow::Borrowed(&M{}) }},",
+            t, ROUNDS_F, rounds_p, n, n
+        )
+        .unwrap();
+    }
+    writeln!(out, "/// Parameters by number of inputs, starting at one input\nstatic PARAMS: [PoseidonParams; {}] = [\n{}];", ROUNDS_P.len(), params).unwrap();
+
+    let path = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("poseidon_constants.rs");
+    std::fs::write(path, out).unwrap();
+}
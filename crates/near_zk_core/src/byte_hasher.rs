@@ -0,0 +1,144 @@
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{de, Deserializer, Serializer};
+#[cfg(not(feature = "near"))]
+use sha3::Digest;
+
+/// 32 byte hash, serialized as a `0x` prefixed hex string
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+pub struct H256(pub [u8; 32]);
+
+impl H256 {
+    pub fn to_hex(&self) -> String {
+        once("0x".to_string()).chain(self.0.iter().map(|v| format!("{:02x}", v))).collect()
+    }
+
+    pub fn from_hex(value: &str) -> Option<Self> {
+        let value = value.strip_prefix("0x").unwrap_or(value);
+        if value.len() != 64 || !value.is_ascii() {
+            return None;
+        }
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).ok()?;
+        }
+        Some(Self(bytes))
+    }
+}
+
+impl Debug for H256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for H256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for H256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <String as Deserialize>::deserialize(deserializer)?;
+        H256::from_hex(&value).ok_or_else(|| de::Error::custom("expected 32 bytes of hex"))
+    }
+}
+
+/// Byte oriented hash function of a secondary commitment
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ByteHashKind {
+    Keccak256,
+    Sha256,
+}
+
+/// Keccak-256 over 32 byte hashes, using the `keccak256` host function with the `near` feature and the `sha3` crate otherwise.
+/// Nodes are `keccak256(left ++ right)`, matching `keccak256(abi.encodePacked(left, right))` on EVM.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Keccak256Hash;
+
+/// SHA-256 over 32 byte hashes, using the `sha256` host function with the `near` feature and the `sha2` crate otherwise
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Sha256Hash;
+
+/// Domain tag of the domain separated byte hashes, `level + 1` as a big endian word
+/// like the capacity element of `PoseidonHash`
+fn domain_tag(level: usize) -> [u8; 32] {
+    let mut tag = [0u8; 32];
+    U256::from(level + 1).to_big_endian(&mut tag);
+    tag
+}
+
+#[cfg(feature = "near")]
+fn keccak256(parts: &[&[u8]]) -> H256 {
+    H256(near_sdk::env::keccak256_array(&parts.concat()))
+}
+
+#[cfg(feature = "near")]
+fn sha256(parts: &[&[u8]]) -> H256 {
+    H256(near_sdk::env::sha256_array(&parts.concat()))
+}
+
+#[cfg(not(feature = "near"))]
+fn keccak256(parts: &[&[u8]]) -> H256 {
+    digest::<sha3::Keccak256>(parts)
+}
+
+#[cfg(not(feature = "near"))]
+fn sha256(parts: &[&[u8]]) -> H256 {
+    digest::<sha2::Sha256>(parts)
+}
+
+#[cfg(not(feature = "near"))]
+fn digest<D: Digest>(parts: &[&[u8]]) -> H256 {
+    let mut hasher = D::new();
+    parts.iter().for_each(|part| hasher.update(part));
+    H256(hasher.finalize().as_slice().try_into().unwrap())
+}
+
+impl Hasher for Keccak256Hash {
+    type Hash = H256;
+
+    fn hash_node(left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        keccak256(&[&left.0, &right.0])
+    }
+
+    fn hash_leaf(value: &Self::Hash) -> Self::Hash {
+        keccak256(&[&domain_tag(0), &value.0])
+    }
+
+    fn hash_node_at(level: usize, left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        keccak256(&[&domain_tag(level), &left.0, &right.0])
+    }
+
+    fn hash_to_bytes(hash: &Self::Hash) -> [u8; 32] {
+        hash.0
+    }
+}
+
+impl Hasher for Sha256Hash {
+    type Hash = H256;
+
+    fn hash_node(left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        sha256(&[&left.0, &right.0])
+    }
+
+    fn hash_leaf(value: &Self::Hash) -> Self::Hash {
+        sha256(&[&domain_tag(0), &value.0])
+    }
+
+    fn hash_node_at(level: usize, left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        sha256(&[&domain_tag(level), &left.0, &right.0])
+    }
+
+    fn hash_to_bytes(hash: &Self::Hash) -> [u8; 32] {
+        hash.0
+    }
+}
@@ -0,0 +1,326 @@
+use crate::*;
+use crate::merkle_tree::Proof as MerkleProof;
+
+/// Error building circuit inputs
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CircuitInputError(pub String);
+
+impl std::fmt::Display for CircuitInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+fn error<T>(message: impl Into<String>) -> Result<T, CircuitInputError> {
+    Err(CircuitInputError(message.into()))
+}
+
+/// Encodes an account id as the `new_owner` signal: its bytes as a big endian integer,
+/// which `Contract::recover` decodes with `decode_account_id`.
+pub fn encode_account_id(account_id: &str) -> Result<U256, CircuitInputError> {
+    if !is_valid_account_id(account_id) {
+        return error(format!("Invalid account id {}", account_id));
+    }
+    let bytes = account_id.as_bytes();
+    if bytes.len() > SPONGE_CHUNK_BYTES {
+        return error(format!("Account id longer than {} bytes", SPONGE_CHUNK_BYTES));
+    }
+    Ok(U256::from_big_endian(bytes))
+}
+
+/// NEAR account id rules: 2 to 64 characters, lowercase alphanumeric parts joined by `.`, `-` or `_`
+fn is_valid_account_id(account_id: &str) -> bool {
+    let separator = |c: char| matches!(c, '.' | '-' | '_');
+    (2..=64).contains(&account_id.len())
+        && account_id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || separator(c))
+        && !account_id.starts_with(separator)
+        && !account_id.ends_with(separator)
+        && account_id.as_bytes().windows(2).all(|w| !(separator(w[0] as char) && separator(w[1] as char)))
+}
+
+/// Where the Merkle path of a question comes from
+pub enum PathSource {
+    /// Tree and question records of a contract, as decoded from its state
+    Tree(Box<MerkleTree<PoseidonHash>>, Vec<Option<Question>>),
+    /// Result of the `get_proof_path` view, `[root, pathIndices, siblings]`
+    ProofPath(U256, MerkleProof<PoseidonHash>),
+}
+
+impl PathSource {
+    pub fn from_proof_path_json(json: &str) -> Result<Self, CircuitInputError> {
+        let (root, path_indices, siblings): (String, Vec<String>, Vec<String>) =
+            serde_json::from_str(json).map_err(|e| CircuitInputError(e.to_string()))?;
+        Self::from_proof_path(&root, &path_indices, &siblings)
+    }
+
+    /// Takes the parts of a `get_proof_path` result, decimal strings
+    pub fn from_proof_path(root: &str, path_indices: &[String], siblings: &[String]) -> Result<Self, CircuitInputError> {
+        if path_indices.len() != siblings.len() {
+            return error("pathIndices and siblings differ in length");
+        }
+        let branches = path_indices
+            .iter()
+            .zip(siblings)
+            .map(|(index, sibling)| {
+                let sibling = parse_u256(sibling)?;
+                match index.as_str() {
+                    "0" => Ok(Branch::Left(sibling)),
+                    "1" => Ok(Branch::Right(sibling)),
+                    _ => error("Path index is not 0 or 1"),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(PathSource::ProofPath(parse_u256(root)?, MerkleProof(branches)))
+    }
+
+    /// Root, path and stored record of a question. Only a state dump has the record.
+    fn lookup(&self, question: U256) -> Result<(U256, MerkleProof<PoseidonHash>, Option<Question>), CircuitInputError> {
+        match self {
+            PathSource::Tree(tree, questions) => {
+                let value = question.to_string();
+                let index = questions
+                    .iter()
+                    .position(|v| v.as_ref().map(|v| v.value.as_str()) == Some(value.as_str()))
+                    .ok_or_else(|| CircuitInputError("Question not found in the contract state".to_string()))?;
+                if tree.mode() != TreeMode::Legacy {
+                    return error("Circuits only support the legacy tree mode");
+                }
+                let proof = tree.proof(index).unwrap();
+                Ok((tree.root(), proof, questions[index].clone()))
+            }
+            PathSource::ProofPath(root, proof) => Ok((*root, proof.clone(), None)),
+        }
+    }
+}
+
+fn parse_u256(value: &str) -> Result<U256, CircuitInputError> {
+    U256::from_dec_str(value).map_err(|_| CircuitInputError(format!("Invalid number {}", value)))
+}
+
+/// Question and answer text of one leaf, with the salt of a hardened leaf.
+/// `scheme` and `normalization` default to the stored question record when a state dump is given.
+#[derive(Clone, Debug, Default)]
+pub struct AnswerInput {
+    pub question: String,
+    pub answer: String,
+    pub salt: Option<U256>,
+    pub scheme: Option<LeafScheme>,
+    pub normalization: Option<NormalizationProfile>,
+}
+
+impl AnswerInput {
+    pub fn new(question: &str, answer: &str) -> Self {
+        Self { question: question.to_string(), answer: answer.to_string(), ..Default::default() }
+    }
+
+    /// Leaf witness of the answer, `record` supplies the scheme and normalization not set here
+    pub fn witness(&self, record: Option<&Question>) -> Result<LeafWitness, CircuitInputError> {
+        let scheme = self.scheme.or(record.map(|v| v.scheme)).unwrap_or_default();
+        let normalization = self.normalization.or(record.map(|v| v.normalization)).unwrap_or_default();
+        if scheme.rounds() > MAX_HARDENED_ROUNDS {
+            return error("Too many hardening rounds");
+        }
+        let salt = match (scheme, self.salt) {
+            (LeafScheme::Legacy, _) => U256::zero(),
+            (_, Some(salt)) => salt,
+            (_, None) => return error("Hardened leaf needs a salt"),
+        };
+        Ok(scheme.witness(QuestionCodec::encode(&self.question), encode_answer(&self.answer, normalization), salt))
+    }
+}
+
+/// Input of `circuits/recover.circom`, or `recover_hardened.circom` when `salt` and `rounds` are set
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct RecoverInput {
+    #[serde(flatten)]
+    pub path: MerkleProof<PoseidonHash>,
+    pub root: U256,
+    pub question: U256,
+    pub answer: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rounds: Option<U256>,
+    pub new_owner: U256,
+}
+
+impl RecoverInput {
+    /// Builds the input and checks that the answer leads to the root
+    pub fn new(source: &PathSource, answer: &AnswerInput, new_owner: &str) -> Result<Self, CircuitInputError> {
+        let question = QuestionCodec::encode(&answer.question);
+        let (root, path, record) = source.lookup(question)?;
+        let witness = answer.witness(record.as_ref())?;
+        check_root(&path, &witness, root)?;
+        let hardened = witness.scheme().map_err(CircuitInputError)? != LeafScheme::Legacy;
+        Ok(Self {
+            path,
+            root,
+            question: witness.question,
+            answer: witness.answer,
+            salt: hardened.then_some(witness.salt),
+            rounds: hardened.then_some(witness.rounds),
+            new_owner: encode_account_id(new_owner)?,
+        })
+    }
+
+    /// Public signals of the proof, `[nullifier, root, question, new_owner]`
+    pub fn public_signals(&self) -> Vec<U256> {
+        vec![self.nullifier(), self.root, self.question, self.new_owner]
+    }
+
+    pub fn nullifier(&self) -> U256 {
+        LeafWitness {
+            question: self.question,
+            answer: self.answer,
+            salt: self.salt.unwrap_or_default(),
+            rounds: self.rounds.unwrap_or_default(),
+        }
+        .nullifier()
+        .expect("Rounds checked by new")
+    }
+}
+
+/// Input of `circuits/recover_aggregate.circom`, answers to several questions under one root.
+/// Every leaf takes the hardened signals, a zero salt and zero rounds for legacy leaves.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RecoverAggregateInput {
+    pub paths: Vec<MerkleProof<PoseidonHash>>,
+    pub root: U256,
+    pub leaves: Vec<LeafWitness>,
+    pub new_owner: U256,
+}
+
+impl RecoverAggregateInput {
+    /// Builds the input from the path source and answer of each question, checking they lead to one root
+    pub fn new(answers: &[(&PathSource, &AnswerInput)], new_owner: &str) -> Result<Self, CircuitInputError> {
+        if answers.is_empty() {
+            return error("No answers");
+        }
+        let (mut paths, mut leaves, mut roots) = (Vec::new(), Vec::new(), Vec::new());
+        for (source, answer) in answers {
+            let question = QuestionCodec::encode(&answer.question);
+            if leaves.iter().any(|v: &LeafWitness| v.question == question) {
+                return error("Questions must be distinct");
+            }
+            let (root, path, record) = source.lookup(question)?;
+            let witness = answer.witness(record.as_ref())?;
+            check_root(&path, &witness, root)?;
+            paths.push(path);
+            leaves.push(witness);
+            roots.push(root);
+        }
+        if roots.iter().any(|v| v != &roots[0]) {
+            return error("Paths have different roots");
+        }
+        Ok(Self { paths, root: roots[0], leaves, new_owner: encode_account_id(new_owner)? })
+    }
+
+    /// Public signals of the proof, `[nullifier_0, .., nullifier_k-1, root, question_0, .., question_k-1, new_owner]`
+    pub fn public_signals(&self) -> Vec<U256> {
+        let nullifiers = self.leaves.iter().map(|v| v.nullifier().expect("Rounds checked by new"));
+        let questions = self.leaves.iter().map(|v| v.question);
+        nullifiers.chain(once(self.root)).chain(questions).chain(once(self.new_owner)).collect()
+    }
+}
+
+#[derive(Serialize)]
+struct RecoverAggregateJson {
+    #[serde(rename = "pathIndices")]
+    path_indices: Vec<Vec<U256>>,
+    siblings: Vec<Vec<U256>>,
+    root: U256,
+    question: Vec<U256>,
+    answer: Vec<U256>,
+    salt: Vec<U256>,
+    rounds: Vec<U256>,
+    new_owner: U256,
+}
+
+impl Serialize for RecoverAggregateInput {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let siblings = self.paths.iter().map(|path| {
+            path.0.iter().map(|branch| match branch {
+                Branch::Left(sibling) | Branch::Right(sibling) => *sibling,
+            }).collect()
+        });
+        RecoverAggregateJson {
+            path_indices: self.paths.iter().map(MerkleProof::path_index).collect(),
+            siblings: siblings.collect(),
+            root: self.root,
+            question: self.leaves.iter().map(|v| v.question).collect(),
+            answer: self.leaves.iter().map(|v| v.answer).collect(),
+            salt: self.leaves.iter().map(|v| v.salt).collect(),
+            rounds: self.leaves.iter().map(|v| v.rounds).collect(),
+            new_owner: self.new_owner,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Input of `circuits/update.circom`, or `update_hardened.circom` when either leaf is hardened
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInput {
+    #[serde(flatten)]
+    pub path: MerkleProof<PoseidonHash>,
+    pub root: U256,
+    pub old_question: U256,
+    pub old_answer: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_salt: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_rounds: Option<U256>,
+    pub new_question: U256,
+    pub new_answer: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_salt: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_rounds: Option<U256>,
+}
+
+impl UpdateInput {
+    pub fn new(source: &PathSource, old: &AnswerInput, new: &AnswerInput) -> Result<Self, CircuitInputError> {
+        let (root, path, record) = source.lookup(QuestionCodec::encode(&old.question))?;
+        let old = old.witness(record.as_ref())?;
+        let new = new.witness(None)?;
+        check_root(&path, &old, root)?;
+        let hardened = old.scheme().map_err(CircuitInputError)? != LeafScheme::Legacy
+            || new.scheme().map_err(CircuitInputError)? != LeafScheme::Legacy;
+        Ok(Self {
+            path,
+            root,
+            old_question: old.question,
+            old_answer: old.answer,
+            old_salt: hardened.then_some(old.salt),
+            old_rounds: hardened.then_some(old.rounds),
+            new_question: new.question,
+            new_answer: new.answer,
+            new_salt: hardened.then_some(new.salt),
+            new_rounds: hardened.then_some(new.rounds),
+        })
+    }
+
+    /// Public signals of the proof, `[leaf, root, oldQuestion, newQuestion]`
+    pub fn public_signals(&self) -> Vec<U256> {
+        vec![self.new_leaf(), self.root, self.old_question, self.new_question]
+    }
+
+    /// New leaf, the output signal of the update circuit
+    pub fn new_leaf(&self) -> U256 {
+        LeafWitness {
+            question: self.new_question,
+            answer: self.new_answer,
+            salt: self.new_salt.unwrap_or_default(),
+            rounds: self.new_rounds.unwrap_or_default(),
+        }
+        .leaf()
+        .expect("Rounds checked by new")
+    }
+}
+
+fn check_root(path: &MerkleProof<PoseidonHash>, witness: &LeafWitness, root: U256) -> Result<(), CircuitInputError> {
+    if path.root(witness.leaf().map_err(CircuitInputError)?) != root {
+        return error("Answer does not match the stored leaf");
+    }
+    Ok(())
+}
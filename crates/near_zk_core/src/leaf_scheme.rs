@@ -0,0 +1,93 @@
+use crate::*;
+
+/// Largest round count supported by the `recover_hardened` and `update_hardened` circuits.
+///
+/// The circuits unroll all `maxRounds` rounds, each a `Poseidon(2)` of about 240 constraints, so proving costs
+/// the same for every leaf and grows with this bound. 16 rounds add about 4k constraints to each leaf.
+/// The rounds do not carry the protection: brute forcing a hardened leaf needs the secret salt, and once it
+/// leaks 16 hashes per guess only slow an attacker by a constant factor. `rounds` is a tunable bound, not a
+/// password hashing cost.
+pub const MAX_HARDENED_ROUNDS: u32 = 16;
+
+/// How a leaf commits to a question and its answer.
+///
+/// `Legacy` leaves are `Poseidon(question, answer)`, which can be brute forced offline for short
+/// answers because the question is public. `Hardened` leaves first stretch the answer with a
+/// salt that only the user keeps,
+///
+/// ```text
+/// key_0 = answer, key_{i+1} = Poseidon(key_i, salt),
+/// leaf = Poseidon(question, key_rounds), nullifier = Poseidon(key_rounds),
+/// ```
+///
+/// so guessing needs the salt and `rounds` hashes per guess. With zero rounds this is the legacy leaf, so the
+/// hardened circuits also prove legacy leaves and their verification keys can replace the legacy ones.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LeafScheme {
+    #[default]
+    Legacy,
+    Hardened { rounds: u32 },
+}
+
+impl LeafScheme {
+    pub fn rounds(&self) -> u32 {
+        match self {
+            LeafScheme::Legacy => 0,
+            LeafScheme::Hardened { rounds } => *rounds,
+        }
+    }
+
+    pub fn assert_valid(&self) {
+        assert!(self.rounds() <= MAX_HARDENED_ROUNDS, "Too many hardening rounds");
+    }
+
+    /// Stretched answer, the answer itself for legacy leaves
+    pub fn key(&self, answer: U256, salt: U256) -> U256 {
+        (0..self.rounds()).fold(answer, |key, _| hash2(key, salt))
+    }
+
+    pub fn leaf(&self, question: U256, answer: U256, salt: U256) -> U256 {
+        hash2(question, self.key(answer, salt))
+    }
+
+    pub fn nullifier(&self, answer: U256, salt: U256) -> U256 {
+        hash1(self.key(answer, salt))
+    }
+
+    pub fn witness(&self, question: U256, answer: U256, salt: U256) -> LeafWitness {
+        self.assert_valid();
+        LeafWitness { question, answer, salt, rounds: self.rounds().into() }
+    }
+}
+
+/// Private leaf inputs of the hardened circuits, serialized with the `HardenedLeaf` signal names.
+/// Legacy leaves use a zero salt and zero rounds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LeafWitness {
+    pub question: U256,
+    pub answer: U256,
+    pub salt: U256,
+    pub rounds: U256,
+}
+
+impl LeafWitness {
+    /// Scheme of the leaf, failing when `rounds` exceeds `MAX_HARDENED_ROUNDS`
+    pub fn scheme(&self) -> Result<LeafScheme, String> {
+        match u32::try_from(self.rounds) {
+            Ok(0) => Ok(LeafScheme::Legacy),
+            Ok(rounds) if rounds <= MAX_HARDENED_ROUNDS => Ok(LeafScheme::Hardened { rounds }),
+            _ => Err("Too many hardening rounds".to_string()),
+        }
+    }
+
+    pub fn leaf(&self) -> Result<U256, String> {
+        Ok(self.scheme()?.leaf(self.question, self.answer, self.salt))
+    }
+
+    pub fn nullifier(&self) -> Result<U256, String> {
+        Ok(self.scheme()?.nullifier(self.answer, self.salt))
+    }
+}
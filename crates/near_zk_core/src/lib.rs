@@ -0,0 +1,44 @@
+//! Poseidon, Merkle trees, question encoding and Groth16 verification of circom proofs, shared by
+//! the contract and off-chain services. The `borsh` and `serde` features add the contract's encodings.
+
+use std::{
+    fmt::Debug,
+    str::FromStr,
+    iter::{once, repeat_n, successors},
+};
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use ark_groth16::VerifyingKey;
+use ark_bn254::{Fr, Fq, Fq2, G1Affine, G2Affine, G1Projective, G2Projective, Bn254};
+use ark_ff::biginteger::BigInteger256;
+
+mod proof;
+mod verification_key;
+
+mod poseidon;
+mod poseidon_grain;
+mod merkle_tree;
+mod byte_hasher;
+mod utils;
+mod question_codec;
+mod leaf_scheme;
+mod normalize;
+#[cfg(feature = "serde")]
+mod circuit_input;
+
+pub use proof::*;
+pub use verification_key::*;
+
+pub use poseidon::*;
+pub use merkle_tree::*;
+pub use byte_hasher::*;
+pub use utils::*;
+pub use question_codec::*;
+pub use leaf_scheme::*;
+pub use normalize::*;
+#[cfg(feature = "serde")]
+pub use circuit_input::*;
@@ -0,0 +1,629 @@
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{de, de::DeserializeOwned, Deserializer, Serializer};
+
+/// Borsh encoding of hashes, required with the `borsh` feature
+#[cfg(feature = "borsh")]
+pub trait BorshHash: BorshDeserialize + BorshSerialize {}
+#[cfg(feature = "borsh")]
+impl<T: BorshDeserialize + BorshSerialize> BorshHash for T {}
+#[cfg(not(feature = "borsh"))]
+pub trait BorshHash {}
+#[cfg(not(feature = "borsh"))]
+impl<T> BorshHash for T {}
+
+/// JSON encoding of hashes, required with the `serde` feature
+#[cfg(feature = "serde")]
+pub trait SerdeHash: Serialize + DeserializeOwned {}
+#[cfg(feature = "serde")]
+impl<T: Serialize + DeserializeOwned> SerdeHash for T {}
+#[cfg(not(feature = "serde"))]
+pub trait SerdeHash {}
+#[cfg(not(feature = "serde"))]
+impl<T> SerdeHash for T {}
+
+/// Hash types, values and algorithms for a Merkle tree
+pub trait Hasher {
+    /// Type of the leaf and node hashes
+    type Hash: Clone + Eq + Debug + BorshHash + SerdeHash;
+
+    /// Compute the hash of an intermediate node
+    fn hash_node(left: &Self::Hash, right: &Self::Hash) -> Self::Hash;
+
+    /// Compute the hash of a leaf from its value, in domain separated trees
+    fn hash_leaf(value: &Self::Hash) -> Self::Hash;
+
+    /// Compute the hash of an intermediate node `level` layers above the leaves,
+    /// in domain separated trees
+    fn hash_node_at(level: usize, left: &Self::Hash, right: &Self::Hash) -> Self::Hash;
+
+    /// 32 byte big endian encoding of a hash, mirrored into secondary commitments
+    fn hash_to_bytes(hash: &Self::Hash) -> [u8; 32];
+}
+
+/// How leaves and intermediate nodes of a tree are hashed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TreeMode {
+    /// Leaves are stored as given and nodes use `Hasher::hash_node`,
+    /// matching `SecretProtectionTree` in `circuits/tree.circom`
+    Legacy,
+
+    /// Leaves go through `Hasher::hash_leaf` and nodes use `Hasher::hash_node_at`,
+    /// matching `SecretProtectionTreeDS` in `circuits/tree.circom`
+    DomainSeparated,
+}
+
+impl TreeMode {
+    /// Hash stored in the tree for a leaf value
+    pub fn leaf<H: Hasher>(self, value: &H::Hash) -> H::Hash {
+        match self {
+            TreeMode::Legacy => value.clone(),
+            TreeMode::DomainSeparated => H::hash_leaf(value),
+        }
+    }
+
+    /// Hash of a node `level` layers above the leaves
+    pub fn node<H: Hasher>(self, level: usize, left: &H::Hash, right: &H::Hash) -> H::Hash {
+        match self {
+            TreeMode::Legacy => H::hash_node(left, right),
+            TreeMode::DomainSeparated => H::hash_node_at(level, left, right),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound(deserialize = "")))]
+pub struct MerkleTree<H: Hasher> {
+    /// Depth of the tree, # of layers including leaf layer
+    depth: usize,
+
+    /// Leaf and node hashing
+    mode: TreeMode,
+
+    /// Hash value of empty subtrees of given depth, starting at leaf level
+    empty: Vec<H::Hash>,
+
+    /// Hash values of tree nodes and leaves, breadth first order
+    nodes: Vec<H::Hash>,
+
+    /// Byte hash tree over the same leaves, kept in sync on every update
+    secondary: Option<Box<SecondaryTree>>,
+}
+
+/// Stored layout of `MerkleTree` before tree modes and secondary trees, read when migrating a contract
+#[cfg(feature = "borsh")]
+#[derive(Clone, PartialEq, Eq, Debug, BorshDeserialize, BorshSerialize)]
+pub struct LegacyMerkleTree<H: Hasher> {
+    pub depth: usize,
+    pub empty: Vec<H::Hash>,
+    pub nodes: Vec<H::Hash>,
+}
+
+#[cfg(feature = "borsh")]
+impl<H: Hasher> From<LegacyMerkleTree<H>> for MerkleTree<H> {
+    /// The same tree in legacy mode, without a secondary commitment
+    fn from(tree: LegacyMerkleTree<H>) -> Self {
+        let num_nodes = u32::try_from(tree.depth).ok().and_then(|depth| 1usize.checked_shl(depth)).map(|v| v - 1);
+        assert!(tree.empty.len() == tree.depth && num_nodes == Some(tree.nodes.len()), "Invalid legacy tree");
+        Self { depth: tree.depth, mode: TreeMode::Legacy, empty: tree.empty, nodes: tree.nodes, secondary: None }
+    }
+}
+
+/// Secondary commitment to the leaves of a tree.
+/// Its leaf values are the 32 byte encodings of the primary leaf hashes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SecondaryTree {
+    Keccak256(MerkleTree<Keccak256Hash>),
+    Sha256(MerkleTree<Sha256Hash>),
+}
+
+/// Element of a Merkle proof
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Branch<H: Hasher> {
+    /// Left branch taken, value is the right sibling hash.
+    Left(H::Hash),
+
+    /// Right branch taken, value is the left sibling hash.
+    Right(H::Hash),
+}
+
+/// Merkle proof path, bottom to top.
+/// Serialized like the circuit inputs, as `pathIndices` and `siblings`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Proof<H: Hasher>(pub Vec<Branch<H>>);
+
+/// Merkle proof for several leaves against one root.
+/// Siblings shared between the paths, or computable from the proven leaves, are not repeated.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound(deserialize = "")))]
+pub struct MultiProof<H: Hasher> {
+    /// Depth of the tree, # of layers including leaf layer
+    pub depth: usize,
+
+    /// Proven leaf indices, strictly increasing
+    pub indices: Vec<usize>,
+
+    /// Sibling hashes not derivable from the proven leaves, bottom to top
+    /// and left to right within a layer
+    pub hashes: Vec<H::Hash>,
+}
+
+/// For a given node index, return the parent node index
+/// Returns None if there is no parent (root node)
+const fn parent(index: usize) -> Option<usize> {
+    if index == 0 {
+        None
+    } else {
+        Some(((index + 1) >> 1) - 1)
+    }
+}
+
+/// For a given node index, return index of the first (left) child.
+const fn first_child(index: usize) -> usize {
+    (index << 1) + 1
+}
+
+const fn depth(index: usize) -> usize {
+    // `n.next_power_of_two()` will return `n` iff `n` is a power of two.
+    // The extra offset corrects this.
+    (index + 2).next_power_of_two().trailing_zeros() as usize - 1
+}
+
+impl<H: Hasher> MerkleTree<H> {
+    /// Creates a new `MerkleTree`
+    /// * `depth` - The depth of the tree, including the root. This is 1 greater
+    ///   than the `treeLevels` argument to the Semaphore contract.
+    pub fn new(depth: usize, initial_leaf: H::Hash) -> Self {
+        Self::new_with_mode(depth, initial_leaf, TreeMode::Legacy)
+    }
+
+    /// Creates a new `MerkleTree` hashing leaves and nodes according to `mode`.
+    /// In domain separated mode `initial_leaf` is a leaf value and gets hashed.
+    pub fn new_with_mode(depth: usize, initial_leaf: H::Hash, mode: TreeMode) -> Self {
+        // Compute empty node values, leaf to root
+        let mut level = 0;
+        let empty = successors(Some(mode.leaf::<H>(&initial_leaf)), |prev| {
+            level += 1;
+            Some(mode.node::<H>(level, prev, prev))
+        })
+        .take(depth)
+        .collect::<Vec<_>>();
+
+        let nodes = empty_nodes(&empty);
+        debug_assert!(nodes.len() == (1 << depth) - 1);
+
+        Self {
+            depth,
+            mode,
+            empty,
+            nodes,
+            secondary: None,
+        }
+    }
+
+    /// Adds a secondary commitment of the current leaves using a byte hash
+    pub fn with_secondary(mut self, kind: ByteHashKind) -> Self {
+        let leaves = self.leaves().iter().map(|hash| H256(H::hash_to_bytes(hash))).collect::<Vec<_>>();
+        let empty_leaf = H256(H::hash_to_bytes(&self.empty_leaf()));
+        let mut secondary = SecondaryTree::new(kind, self.depth, empty_leaf, self.mode);
+        secondary.set_range(0, &leaves);
+        self.secondary = Some(Box::new(secondary));
+        self
+    }
+
+    pub fn secondary(&self) -> Option<&SecondaryTree> {
+        self.secondary.as_deref()
+    }
+
+    /// Resets every leaf, keeping depth, mode and secondary commitment
+    pub fn clear(&mut self) {
+        self.nodes = empty_nodes(&self.empty);
+        if let Some(secondary) = self.secondary.as_mut() {
+            secondary.clear();
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn mode(&self) -> TreeMode {
+        self.mode
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.depth
+            .checked_sub(1)
+            .map(|n| 1 << n)
+            .unwrap_or_default()
+    }
+
+    pub fn root(&self) -> H::Hash {
+        self.nodes[0].clone()
+    }
+
+    /// Hash value of an empty leaf
+    pub fn empty_leaf(&self) -> H::Hash {
+        self.empty[0].clone()
+    }
+
+    /// Sets a leaf value, hashed first in domain separated mode
+    pub fn set(&mut self, leaf: usize, hash: H::Hash) {
+        self.set_range(leaf, once(hash));
+    }
+
+    /// Resets a leaf to the empty leaf value
+    pub fn reset(&mut self, leaf: usize) {
+        self.set_hashes(leaf, once(self.empty_leaf()));
+    }
+
+    /// Moves the hash at leaf `from` to leaf `to`, leaving `from` empty
+    pub fn move_leaf(&mut self, from: usize, to: usize) {
+        let hash = self.leaves()[from].clone();
+        self.set_hashes(to, once(hash));
+        self.reset(from);
+    }
+
+    pub fn set_range<I: IntoIterator<Item = H::Hash>>(&mut self, start: usize, hashes: I) {
+        let mode = self.mode;
+        self.set_hashes(start, hashes.into_iter().map(|value| mode.leaf::<H>(&value)));
+    }
+
+    /// Writes leaf hashes as they are stored in the tree
+    fn set_hashes<I: IntoIterator<Item = H::Hash>>(&mut self, start: usize, hashes: I) {
+        let index = self.num_leaves() + start - 1;
+        let mut count = 0;
+        // TODO: Error/panic when hashes is longer than available leafs
+        for (leaf, hash) in self.nodes[index..].iter_mut().zip(hashes) {
+            *leaf = hash;
+            count += 1;
+        }
+        if count != 0 {
+            self.update_nodes(index, index + (count - 1));
+            if let Some(secondary) = self.secondary.as_mut() {
+                let leaves = self.nodes[index..index + count].iter().map(|hash| H256(H::hash_to_bytes(hash))).collect::<Vec<_>>();
+                secondary.set_range(start, &leaves);
+            }
+        }
+    }
+
+    fn update_nodes(&mut self, start: usize, end: usize) {
+        debug_assert_eq!(depth(start), depth(end));
+        if let (Some(start), Some(end)) = (parent(start), parent(end)) {
+            let level = self.depth - 1 - depth(start);
+            for parent in start..=end {
+                let child = first_child(parent);
+                self.nodes[parent] = self.mode.node::<H>(level, &self.nodes[child], &self.nodes[child + 1]);
+            }
+            self.update_nodes(start, end);
+        }
+    }
+
+    pub fn proof(&self, leaf: usize) -> Option<Proof<H>> {
+        if leaf >= self.num_leaves() {
+            return None;
+        }
+        let mut index = self.num_leaves() + leaf - 1;
+        let mut path = Vec::with_capacity(self.depth);
+        while let Some(parent) = parent(index) {
+            // Add proof for node at index to parent
+            path.push(match index & 1 {
+                1 => Branch::Left(self.nodes[index + 1].clone()),
+                0 => Branch::Right(self.nodes[index - 1].clone()),
+                _ => unreachable!(),
+            });
+            index = parent;
+        }
+        Some(Proof(path))
+    }
+
+    /// Checks a proof for a leaf value against the current root
+    pub fn verify(&self, hash: H::Hash, proof: &Proof<H>) -> bool {
+        proof.verify_with_mode(self.mode, hash, &self.root())
+    }
+
+    /// Generates a proof for several leaves at once.
+    /// Returns None if `leaves` is empty or contains an index out of range.
+    pub fn multi_proof(&self, leaves: &[usize]) -> Option<MultiProof<H>> {
+        let mut indices = leaves.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() || indices.iter().any(|&leaf| leaf >= self.num_leaves()) {
+            return None;
+        }
+
+        let mut hashes = Vec::new();
+        let mut known = indices.clone();
+        let mut offset = self.num_leaves() - 1;
+        while offset > 0 {
+            let mut i = 0;
+            while i < known.len() {
+                let position = known[i];
+                if position & 1 == 0 && known.get(i + 1) == Some(&(position + 1)) {
+                    i += 2;
+                } else {
+                    hashes.push(self.nodes[offset + (position ^ 1)].clone());
+                    i += 1;
+                }
+            }
+            known = known.into_iter().map(|position| position >> 1).collect();
+            known.dedup();
+            offset = parent(offset).unwrap();
+        }
+
+        Some(MultiProof {
+            depth: self.depth,
+            indices,
+            hashes,
+        })
+    }
+
+    pub fn verify_multi(&self, leaves: &[H::Hash], proof: &MultiProof<H>) -> bool {
+        proof.depth == self.depth && proof.verify_with_mode(self.mode, leaves, &self.root())
+    }
+
+    pub fn leaves(&self) -> &[H::Hash] {
+        &self.nodes[(self.num_leaves() - 1)..]
+    }
+}
+
+/// Node values of an empty tree, breadth first, given the empty subtree hashes from leaf to root
+fn empty_nodes<T: Clone>(empty: &[T]) -> Vec<T> {
+    empty
+        .iter()
+        .rev()
+        .enumerate()
+        .flat_map(|(depth, hash)| repeat_n(hash, 1 << depth))
+        .cloned()
+        .collect()
+}
+
+// Borsh is implemented by hand with the layout of a derived enum. The derive bounds
+// every field type, which would recurse through `MerkleTree::secondary`.
+#[cfg(feature = "borsh")]
+impl BorshSerialize for SecondaryTree {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            SecondaryTree::Keccak256(tree) => {
+                BorshSerialize::serialize(&0u8, writer)?;
+                BorshSerialize::serialize(tree, writer)
+            },
+            SecondaryTree::Sha256(tree) => {
+                BorshSerialize::serialize(&1u8, writer)?;
+                BorshSerialize::serialize(tree, writer)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for SecondaryTree {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        match <u8 as BorshDeserialize>::deserialize(buf)? {
+            0 => Ok(SecondaryTree::Keccak256(BorshDeserialize::deserialize(buf)?)),
+            1 => Ok(SecondaryTree::Sha256(BorshDeserialize::deserialize(buf)?)),
+            _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unexpected variant index")),
+        }
+    }
+}
+
+impl SecondaryTree {
+    pub fn new(kind: ByteHashKind, depth: usize, initial_leaf: H256, mode: TreeMode) -> Self {
+        match kind {
+            ByteHashKind::Keccak256 => SecondaryTree::Keccak256(MerkleTree::new_with_mode(depth, initial_leaf, mode)),
+            ByteHashKind::Sha256 => SecondaryTree::Sha256(MerkleTree::new_with_mode(depth, initial_leaf, mode)),
+        }
+    }
+
+    pub fn kind(&self) -> ByteHashKind {
+        match self {
+            SecondaryTree::Keccak256(_) => ByteHashKind::Keccak256,
+            SecondaryTree::Sha256(_) => ByteHashKind::Sha256,
+        }
+    }
+
+    pub fn root(&self) -> H256 {
+        match self {
+            SecondaryTree::Keccak256(tree) => tree.root(),
+            SecondaryTree::Sha256(tree) => tree.root(),
+        }
+    }
+
+    // Takes a slice rather than an iterator, so monomorphizing `MerkleTree::set_range`
+    // does not recurse through the secondary tree.
+    pub fn set_range(&mut self, start: usize, values: &[H256]) {
+        match self {
+            SecondaryTree::Keccak256(tree) => tree.set_range(start, values.iter().cloned()),
+            SecondaryTree::Sha256(tree) => tree.set_range(start, values.iter().cloned()),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        match self {
+            SecondaryTree::Keccak256(tree) => tree.clear(),
+            SecondaryTree::Sha256(tree) => tree.clear(),
+        }
+    }
+}
+
+impl<H: Hasher> Proof<H> {
+    /// Compute the leaf index for this proof
+    pub fn leaf_index(&self) -> usize {
+        self.0.iter().rev().fold(0, |index, branch| match branch {
+            Branch::Left(_) => index << 1,
+            Branch::Right(_) => (index << 1) + 1,
+        })
+    }
+
+    /// Compute path index (TODO: do we want to keep this here?)
+    pub fn path_index(&self) -> Vec<U256> {
+        self.0
+            .iter()
+            .map(|branch| match branch {
+                Branch::Left(_) => U256::from(0),
+                Branch::Right(_) => U256::from(1),
+            })
+            .collect()
+    }
+
+    /// Compute the Merkle root given a leaf hash
+    pub fn root(&self, hash: H::Hash) -> H::Hash {
+        self.root_with_mode(TreeMode::Legacy, hash)
+    }
+
+    /// Compute the Merkle root given a leaf value, hashing as a tree in `mode`
+    pub fn root_with_mode(&self, mode: TreeMode, value: H::Hash) -> H::Hash {
+        self.0.iter().enumerate().fold(mode.leaf::<H>(&value), |hash, (i, branch)| match branch {
+            Branch::Left(sibling) => mode.node::<H>(i + 1, &hash, sibling),
+            Branch::Right(sibling) => mode.node::<H>(i + 1, sibling, &hash),
+        })
+    }
+
+    /// Checks that the proof connects a leaf hash to a root, in a legacy tree
+    pub fn verify(&self, leaf: H::Hash, root: &H::Hash) -> bool {
+        self.verify_with_mode(TreeMode::Legacy, leaf, root)
+    }
+
+    /// Checks that the proof connects a leaf value to a root, hashing as a tree in `mode`
+    pub fn verify_with_mode(&self, mode: TreeMode, leaf: H::Hash, root: &H::Hash) -> bool {
+        &self.root_with_mode(mode, leaf) == root
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofJson<T> {
+    path_indices: Vec<String>,
+    siblings: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<H: Hasher> Serialize for Proof<H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (path_indices, siblings) = self.0.iter().map(|branch| match branch {
+            Branch::Left(sibling) => ("0".to_string(), sibling),
+            Branch::Right(sibling) => ("1".to_string(), sibling),
+        }).unzip();
+        ProofJson { path_indices, siblings }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, H: Hasher> Deserialize<'de> for Proof<H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let proof = ProofJson::<H::Hash>::deserialize(deserializer)?;
+        if proof.path_indices.len() != proof.siblings.len() {
+            return Err(de::Error::custom("pathIndices and siblings differ in length"));
+        }
+        proof.path_indices.into_iter().zip(proof.siblings).map(|(index, sibling)| match index.as_str() {
+            "0" => Ok(Branch::Left(sibling)),
+            "1" => Ok(Branch::Right(sibling)),
+            _ => Err(de::Error::custom("path index must be \"0\" or \"1\"")),
+        }).collect::<Result<_, _>>().map(Proof)
+    }
+}
+
+impl<H: Hasher> MultiProof<H> {
+    /// Compute the Merkle root given the leaf hashes, in the order of `indices`.
+    /// Returns None if the proof is malformed or does not match the number of leaves.
+    pub fn root(&self, leaves: &[H::Hash]) -> Option<H::Hash> {
+        self.root_with_mode(TreeMode::Legacy, leaves)
+    }
+
+    /// Compute the Merkle root given the leaf values, hashing as a tree in `mode`
+    pub fn root_with_mode(&self, mode: TreeMode, leaves: &[H::Hash]) -> Option<H::Hash> {
+        // The depth comes from the caller, so it must not overflow the shift
+        let num_leaves = self.depth.checked_sub(1).and_then(|n| 1usize.checked_shl(u32::try_from(n).ok()?))?;
+        if leaves.is_empty()
+            || leaves.len() != self.indices.len()
+            || self.indices.windows(2).any(|w| w[0] >= w[1])
+            || self.indices.iter().any(|&leaf| leaf >= num_leaves)
+        {
+            return None;
+        }
+
+        let mut siblings = self.hashes.iter();
+        let mut layer: Vec<(usize, H::Hash)> = self.indices.iter().cloned().zip(leaves.iter().map(|v| mode.leaf::<H>(v))).collect();
+        for level in 1..self.depth {
+            let mut next = Vec::with_capacity(layer.len());
+            let mut i = 0;
+            while i < layer.len() {
+                let (position, hash) = &layer[i];
+                let parent = if position & 1 == 0 {
+                    match layer.get(i + 1) {
+                        Some((sibling_position, sibling)) if *sibling_position == position + 1 => {
+                            i += 1;
+                            mode.node::<H>(level, hash, sibling)
+                        },
+                        _ => mode.node::<H>(level, hash, siblings.next()?),
+                    }
+                } else {
+                    mode.node::<H>(level, siblings.next()?, hash)
+                };
+                next.push((position >> 1, parent));
+                i += 1;
+            }
+            layer = next;
+        }
+
+        if siblings.next().is_some() {
+            return None;
+        }
+        layer.pop().map(|(_, root)| root)
+    }
+
+    /// Checks the proof for the given leaf hashes against a root, in a legacy tree
+    pub fn verify(&self, leaves: &[H::Hash], root: &H::Hash) -> bool {
+        self.verify_with_mode(TreeMode::Legacy, leaves, root)
+    }
+
+    /// Checks the proof for the given leaf values against a root, hashing as a tree in `mode`
+    pub fn verify_with_mode(&self, mode: TreeMode, leaves: &[H::Hash], root: &H::Hash) -> bool {
+        self.root_with_mode(mode, leaves).as_ref() == Some(root)
+    }
+}
+
+impl<H> Debug for Branch<H>
+where
+    H: Hasher,
+    H::Hash: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left(arg0) => f.debug_tuple("Left").field(arg0).finish(),
+            Self::Right(arg0) => f.debug_tuple("Right").field(arg0).finish(),
+        }
+    }
+}
+
+impl<H> Debug for Proof<H>
+where
+    H: Hasher,
+    H::Hash: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Proof").field(&self.0).finish()
+    }
+}
+
+impl<H> Debug for MultiProof<H>
+where
+    H: Hasher,
+    H::Hash: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiProof")
+            .field("depth", &self.depth)
+            .field("indices", &self.indices)
+            .field("hashes", &self.hashes)
+            .finish()
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+use unicode_normalization::UnicodeNormalization;
+
+/// Rules applied to an answer before it is encoded
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct NormalizationRules {
+    /// Unicode NFKC, so compatibility forms such as full width letters match their plain form
+    pub nfkc: bool,
+    /// Unicode default case folding
+    pub case_fold: bool,
+    /// Removes every character that is neither alphanumeric nor whitespace
+    pub strip_punctuation: bool,
+    /// Trims the text and replaces each run of whitespace with a single space
+    pub collapse_whitespace: bool,
+}
+
+impl NormalizationRules {
+    /// Applies the rules in order: NFKC, case folding, punctuation stripping, whitespace collapsing.
+    /// Case folding is followed by another NFKC pass, since folding can produce unnormalized text.
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = if self.nfkc { text.nfkc().collect() } else { text.to_string() };
+        if self.case_fold {
+            text = caseless::default_case_fold_str(&text);
+            if self.nfkc {
+                text = text.nfkc().collect();
+            }
+        }
+        if self.strip_punctuation {
+            text.retain(|c| c.is_alphanumeric() || c.is_whitespace());
+        }
+        if self.collapse_whitespace {
+            text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        text
+    }
+}
+
+/// Normalization profile stored with each question, so every client normalizes its answer the same way
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NormalizationProfile {
+    /// Answers are encoded as given, the behavior before profiles were added
+    #[default]
+    Raw,
+    /// NFKC, case folding and whitespace collapsing
+    Standard,
+    /// `Standard` with punctuation stripped as well
+    Loose,
+}
+
+impl NormalizationProfile {
+    pub fn rules(&self) -> NormalizationRules {
+        match self {
+            NormalizationProfile::Raw => NormalizationRules::default(),
+            NormalizationProfile::Standard => NormalizationRules {
+                nfkc: true,
+                case_fold: true,
+                strip_punctuation: false,
+                collapse_whitespace: true,
+            },
+            NormalizationProfile::Loose => NormalizationRules {
+                strip_punctuation: true,
+                ..NormalizationProfile::Standard.rules()
+            },
+        }
+    }
+
+    pub fn normalize(&self, text: &str) -> String {
+        self.rules().apply(text)
+    }
+}
+
+/// Parses the lowercase profile names `raw`, `standard` and `loose` used by the command line tools
+impl FromStr for NormalizationProfile {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "raw" => Ok(NormalizationProfile::Raw),
+            "standard" => Ok(NormalizationProfile::Standard),
+            "loose" => Ok(NormalizationProfile::Loose),
+            _ => Err(format!("Unknown normalization profile {}", name)),
+        }
+    }
+}
+
+/// Normalizes an answer with `profile` and encodes it like question text, see `QuestionCodec`.
+/// The result is the `answer` input of the leaf hash.
+pub fn encode_answer(text: &str, profile: NormalizationProfile) -> U256 {
+    QuestionCodec::encode(&profile.normalize(text))
+}
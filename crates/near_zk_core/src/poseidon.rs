@@ -0,0 +1,204 @@
+use crate::*;
+
+use std::{borrow::Cow, slice::Chunks};
+use ark_ff::{Field, Zero};
+
+/// Largest number of inputs, as in circomlib
+pub const POSEIDON_MAX_INPUTS: usize = 16;
+
+/// Round constants and MDS matrix of a Poseidon permutation over the BN254 scalar field
+pub struct PoseidonParams {
+    /// State width, number of inputs plus the capacity element
+    pub t: usize,
+    pub rounds_f: usize,
+    pub rounds_p: usize,
+
+    /// Round constants, `t` per round
+    pub c: Cow<'static, [Fr]>,
+
+    /// Maximum distance separable matrix, row by row
+    pub m: Cow<'static, [Fr]>,
+}
+
+// `PARAMS`, written by build.rs with `PoseidonParams::generate`
+include!(concat!(env!("OUT_DIR"), "/poseidon_constants.rs"));
+
+impl PoseidonParams {
+    /// Generates the parameters for `t` state elements from the Grain LFSR.
+    /// `poseidon_params` serves the same parameters from tables generated at build time.
+    pub fn generate(t: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        let (c, m) = poseidon_grain::generate(t, rounds_f, rounds_p);
+        Self { t, rounds_f, rounds_p, c: Cow::Owned(c), m: Cow::Owned(m) }
+    }
+
+    /// Round constants of each round
+    pub fn round_constants(&self) -> Chunks<'_, Fr> {
+        self.c.chunks(self.t)
+    }
+
+    /// Rows of the MDS matrix
+    pub fn mds_rows(&self) -> Chunks<'_, Fr> {
+        self.m.chunks(self.t)
+    }
+
+    pub fn permute(&self, state: &mut [Fr]) {
+        assert_eq!(state.len(), self.t);
+        let partial = self.rounds_f / 2..self.rounds_f / 2 + self.rounds_p;
+        for (i, constants) in self.round_constants().enumerate() {
+            // Add round constants
+            state.iter_mut().zip(constants).for_each(|(v, c)| *v += c);
+
+            // SubWords, S-Box: Exponentiate
+            if partial.contains(&i) {
+                state[0] = state[0].pow([5]);
+            } else {
+                state.iter_mut().for_each(|v| *v = v.pow([5]));
+            }
+
+            // MixLayer: Multiply by maximum distance separable matrix
+            let mixed = self.mds_rows()
+                .map(|row| row.iter().zip(state.iter()).fold(Fr::zero(), |sum, (m, v)| sum + *m * v))
+                .collect::<Vec<_>>();
+            state.copy_from_slice(&mixed);
+        }
+    }
+}
+
+/// Parameters for `n_inputs` inputs
+pub fn poseidon_params(n_inputs: usize) -> &'static PoseidonParams {
+    assert!((1..=POSEIDON_MAX_INPUTS).contains(&n_inputs), "Poseidon takes 1 to 16 inputs");
+    &PARAMS[n_inputs - 1]
+}
+
+/// Poseidon of 1 to 16 field elements, compatible with circomlib's `Poseidon(n)`
+pub fn poseidon_fr(inputs: &[Fr]) -> Fr {
+    poseidon_fr_with_domain(Fr::zero(), inputs)
+}
+
+/// Poseidon with the capacity element set to `domain`,
+/// like circomlib's `PoseidonEx(n, 1)` with `initialState = domain`
+pub fn poseidon_fr_with_domain(domain: Fr, inputs: &[Fr]) -> Fr {
+    let params = poseidon_params(inputs.len());
+    let mut state = once(domain).chain(inputs.iter().cloned()).collect::<Vec<_>>();
+    params.permute(&mut state);
+    state[0]
+}
+
+/// Poseidon of 1 to 16 inputs, compatible with circomlib's `Poseidon(n)`.
+/// Panics if an input is not below the field modulus.
+pub fn poseidon(inputs: &[U256]) -> U256 {
+    poseidon_with_domain(U256::zero(), inputs)
+}
+
+pub fn poseidon_with_domain(domain: U256, inputs: &[U256]) -> U256 {
+    let inputs = inputs.iter().map(|v| to_field(*v)).collect::<Vec<_>>();
+    poseidon_fr_with_domain(to_field(domain), &inputs).into()
+}
+
+pub fn hash1(value: U256) -> U256 {
+    poseidon(&[value])
+}
+
+/// Poseidon of one input with the capacity element set to `domain`,
+/// like circomlib's `PoseidonEx(1, 1)` with `initialState = domain`
+pub fn hash1_with_domain(domain: U256, value: U256) -> U256 {
+    poseidon_with_domain(domain, &[value])
+}
+
+pub fn hash2(left: U256, right: U256) -> U256 {
+    poseidon(&[left, right])
+}
+
+/// Poseidon of two inputs with the capacity element set to `domain`,
+/// like circomlib's `PoseidonEx(2, 1)` with `initialState = domain`
+pub fn hash2_with_domain(domain: U256, left: U256, right: U256) -> U256 {
+    poseidon_with_domain(domain, &[left, right])
+}
+
+/// Bytes packed into each field element by `pack_bytes`
+pub const SPONGE_CHUNK_BYTES: usize = 31;
+
+/// State width of the sponge permutation, rate 2 and capacity 1
+const SPONGE_WIDTH: usize = 3;
+
+/// Packs a byte string into field elements: 31 byte chunks read as big endian integers,
+/// the last one possibly shorter, followed by the byte length of the message.
+pub fn pack_bytes(message: &[u8]) -> Vec<Fr> {
+    message
+        .chunks(SPONGE_CHUNK_BYTES)
+        .map(|chunk| to_field(U256::from_big_endian(chunk)))
+        .chain(once(Fr::from(message.len() as u64)))
+        .collect()
+}
+
+/// Poseidon sponge over the 2 input permutation, matching `PoseidonSponge` in `circuits/sponge.circom`.
+///
+/// The capacity element starts at `2^64 + (n_outputs - 1)`, the variable length domain of the
+/// Poseidon paper. Elements are added to the two rate elements and the state is permuted after
+/// every full block, the last block is padded with zeros. Outputs are read from the rate
+/// elements, permuting again whenever more than two are needed.
+#[derive(Clone, Debug)]
+pub struct PoseidonSponge {
+    state: [Fr; SPONGE_WIDTH],
+    pending: Vec<Fr>,
+    absorbed: bool,
+    n_outputs: usize,
+}
+
+impl PoseidonSponge {
+    pub fn new(n_outputs: usize) -> Self {
+        assert!(n_outputs > 0, "Sponge needs at least one output");
+        let domain = Fr::from(u64::MAX) + Fr::from(n_outputs as u64);
+        Self {
+            state: [domain, Fr::zero(), Fr::zero()],
+            pending: Vec::with_capacity(SPONGE_WIDTH - 1),
+            absorbed: false,
+            n_outputs,
+        }
+    }
+
+    pub fn absorb(&mut self, elements: &[Fr]) {
+        for element in elements {
+            self.pending.push(*element);
+            if self.pending.len() == SPONGE_WIDTH - 1 {
+                self.permute_pending();
+            }
+        }
+    }
+
+    /// Absorbs a byte string packed with `pack_bytes`
+    pub fn absorb_bytes(&mut self, message: &[u8]) {
+        self.absorb(&pack_bytes(message));
+    }
+
+    pub fn squeeze(mut self) -> Vec<Fr> {
+        if !self.pending.is_empty() || !self.absorbed {
+            self.permute_pending();
+        }
+        let mut outputs = Vec::with_capacity(self.n_outputs);
+        loop {
+            for element in &self.state[1..] {
+                if outputs.len() == self.n_outputs {
+                    return outputs;
+                }
+                outputs.push(*element);
+            }
+            poseidon_params(SPONGE_WIDTH - 1).permute(&mut self.state);
+        }
+    }
+
+    fn permute_pending(&mut self) {
+        for (state, element) in self.state[1..].iter_mut().zip(self.pending.drain(..)) {
+            *state += element;
+        }
+        poseidon_params(SPONGE_WIDTH - 1).permute(&mut self.state);
+        self.absorbed = true;
+    }
+}
+
+/// Hashes a byte string of any length to one field element
+pub fn poseidon_bytes(message: &[u8]) -> U256 {
+    let mut sponge = PoseidonSponge::new(1);
+    sponge.absorb_bytes(message);
+    sponge.squeeze()[0].into()
+}
@@ -0,0 +1,102 @@
+//! Poseidon parameters from the Grain LFSR, as in the Poseidon reference `generate_parameters_grain.sage`.
+//! `build.rs` includes this file to write the constant tables, the crate keeps it to check them.
+
+use ark_bn254::{Fr, FrParameters};
+use ark_ff::{BigInteger, BigInteger256, Field, FpParameters, PrimeField, Zero};
+
+/// Grain LFSR in self-shrinking mode
+struct Grain {
+    /// 80 bit shift register, bit `i` is the `i`-th oldest bit
+    state: u128,
+}
+
+impl Grain {
+    fn new(t: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        // Prime field, x^5 S-box, 254 bit field size, then the width, round numbers and 30 ones
+        let fields = [
+            (1, 2),
+            (0, 4),
+            (254, 12),
+            (t as u128, 12),
+            (rounds_f as u128, 10),
+            (rounds_p as u128, 10),
+            ((1 << 30) - 1, 30),
+        ];
+        let mut state = 0u128;
+        let mut position = 0;
+        for (value, width) in fields {
+            for i in (0..width).rev() {
+                state |= ((value >> i) & 1) << position;
+                position += 1;
+            }
+        }
+
+        let mut grain = Self { state };
+        for _ in 0..160 {
+            grain.next_raw();
+        }
+        grain
+    }
+
+    fn next_raw(&mut self) -> bool {
+        let s = self.state;
+        let bit = ((s >> 62) ^ (s >> 51) ^ (s >> 38) ^ (s >> 23) ^ (s >> 13) ^ s) & 1;
+        self.state = (s >> 1) | (bit << 79);
+        bit == 1
+    }
+
+    /// Bits are drawn in pairs, the second one is output if the first one is set
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_raw();
+            let bit = self.next_raw();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// Next 254 bits as an integer, most significant bit first
+    fn next_uint(&mut self) -> BigInteger256 {
+        let mut value = BigInteger256::default();
+        for position in (0..254).rev() {
+            if self.next_bit() {
+                value.0[position / 64] |= 1 << (position % 64);
+            }
+        }
+        value
+    }
+}
+
+/// Round constants and MDS matrix for `t` state elements, both flattened row by row.
+/// Round constants are sampled with rejection, the MDS matrix is the Cauchy matrix `1 / (x_i + y_j)`.
+pub fn generate(t: usize, rounds_f: usize, rounds_p: usize) -> (Vec<Fr>, Vec<Fr>) {
+    let mut grain = Grain::new(t, rounds_f, rounds_p);
+
+    let c = (0..(rounds_f + rounds_p) * t)
+        .map(|_| loop {
+            if let Some(value) = Fr::from_repr(grain.next_uint()) {
+                return value;
+            }
+        })
+        .collect();
+
+    let m = loop {
+        let values = (0..2 * t)
+            .map(|_| {
+                let mut value = grain.next_uint();
+                if value >= FrParameters::MODULUS {
+                    value.sub_noborrow(&FrParameters::MODULUS);
+                }
+                Fr::from_repr(value).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let (xs, ys) = values.split_at(t);
+        let distinct = values.iter().enumerate().all(|(i, v)| !values[..i].contains(v));
+        if distinct && xs.iter().all(|x| ys.iter().all(|y| !(*x + y).is_zero())) {
+            break xs.iter().flat_map(|x| ys.iter().map(move |y| (*x + y).inverse().unwrap())).collect();
+        }
+    };
+
+    (c, m)
+}
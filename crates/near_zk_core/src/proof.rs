@@ -1,8 +1,7 @@
 use crate::*;
+use ark_groth16::Proof;
 
-
-#[derive(Deserialize)]
-#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CircomProof {
     pub pi_a: CircomG1Affine,
     pub pi_b: CircomG2Affine,
@@ -29,4 +28,16 @@ impl From<CircomProof> for Proof<Bn254> {
             c: g1_affine(&src.pi_c),
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<Proof<Bn254>> for CircomProof {
+    fn from(src: Proof<Bn254>) -> Self {
+        CircomProof {
+            pi_a: circom_g1(&src.a),
+            pi_b: circom_g2(&src.b),
+            pi_c: circom_g1(&src.c),
+            protocol: "groth16".to_string(),
+            curve: "bn128".to_string(),
+        }
+    }
+}
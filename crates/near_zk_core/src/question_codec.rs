@@ -0,0 +1,93 @@
+use crate::*;
+
+/// Longest question, in UTF-8 bytes, that is stored inline in the question value
+pub const INLINE_QUESTION_BYTES: usize = SPONGE_CHUNK_BYTES;
+
+/// Encoding of question text to the field element used as `question` in the circuits.
+///
+/// - Text of at most 31 bytes without NUL bytes is its UTF-8 bytes read as a big endian integer,
+///   the encoding used before long questions were supported, so existing leaves stay valid.
+/// - Any other text is committed with `poseidon_bytes`, the Poseidon sponge over the 31 byte
+///   chunks of the UTF-8 bytes followed by the byte length, see `PoseidonSponge` in `circuits/sponge.circom`.
+///
+/// Text is not normalized, the exact bytes given are encoded.
+pub struct QuestionCodec;
+
+impl QuestionCodec {
+    pub fn encode(text: &str) -> U256 {
+        let bytes = text.as_bytes();
+        if Self::is_inline(text) {
+            U256::from_big_endian(bytes)
+        } else {
+            poseidon_bytes(bytes)
+        }
+    }
+
+    /// Decodes an inline question value, `None` for values that are not inline text
+    pub fn decode(value: U256) -> Option<String> {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        let start = bytes.iter().position(|v| *v != 0)?;
+        if start == 0 {
+            return None;
+        }
+        let text = String::from_utf8(bytes[start..].to_vec()).ok()?;
+        Self::is_inline(&text).then_some(text)
+    }
+
+    pub fn matches(text: &str, value: &str) -> bool {
+        Self::encode(text).to_string() == value
+    }
+
+    fn is_inline(text: &str) -> bool {
+        text.len() <= INLINE_QUESTION_BYTES && !text.as_bytes().contains(&0)
+    }
+}
+
+/// Security question as stored by the contract
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Question {
+    /// Decimal field element used in the leaf and the public inputs
+    pub value: String,
+    /// Full question text, `None` when only the value was provided
+    pub text: Option<String>,
+    /// Leaf commitment scheme, so clients know which inputs the proofs need
+    pub scheme: LeafScheme,
+    /// Normalization applied to the answer before encoding
+    pub normalization: NormalizationProfile,
+    /// Weight a recover proof of this question counts toward the recovery policy
+    pub weight: u32,
+}
+
+impl Question {
+    pub fn new(value: String, text: Option<String>) -> Self {
+        if let Some(text) = &text {
+            assert!(QuestionCodec::matches(text, &value), "Question text does not match");
+        }
+        Self { value, text, scheme: LeafScheme::Legacy, normalization: NormalizationProfile::Raw, weight: 1 }
+    }
+
+    pub fn with_scheme(mut self, scheme: LeafScheme) -> Self {
+        scheme.assert_valid();
+        self.scheme = scheme;
+        self
+    }
+
+    pub fn with_normalization(mut self, normalization: NormalizationProfile) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        assert!(weight > 0, "Invalid question weight");
+        self.weight = weight;
+        self
+    }
+
+    /// Readable text, falling back to decoding an inline value
+    pub fn readable_text(&self) -> Option<String> {
+        self.text.clone().or_else(|| QuestionCodec::decode(U256::from_dec_str(&self.value).ok()?))
+    }
+}
@@ -0,0 +1,160 @@
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{de, Deserializer, Serializer};
+use ark_bn254::Fq12;
+use ark_ff::{One, PrimeField, Zero};
+
+pub use uint256::U256;
+
+// The expansion of `construct_uint!` trips these lints
+#[allow(clippy::manual_range_contains, clippy::assign_op_pattern)]
+mod uint256 {
+    #[cfg(feature = "borsh")]
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    uint::construct_uint! {
+        #[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+        pub struct U256(4);
+    }
+}
+
+/// Serialized as a decimal string, the encoding circom and snarkjs use for field elements
+#[cfg(feature = "serde")]
+impl Serialize for U256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <String as Deserialize>::deserialize(deserializer)?;
+        U256::from_dec_str(&value).map_err(de::Error::custom)
+    }
+}
+
+/// Error converting a `U256` not below the field modulus into a field element
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NotInFieldError;
+
+impl std::fmt::Display for NotInFieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("value is not below the field modulus")
+    }
+}
+
+/// Both types store four little endian 64 bit limbs, `Fr` in Montgomery form
+impl From<Fr> for U256 {
+    fn from(value: Fr) -> Self {
+        U256(value.into_repr().0)
+    }
+}
+
+impl TryFrom<U256> for Fr {
+    type Error = NotInFieldError;
+
+    fn try_from(value: U256) -> Result<Self, Self::Error> {
+        Fr::from_repr(BigInteger256(value.0)).ok_or(NotInFieldError)
+    }
+}
+
+/// Converts a hash input to a field element, panicking if it is out of range
+pub fn to_field(value: U256) -> Fr {
+    Fr::try_from(value).expect("Value exceeds the field modulus")
+}
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PoseidonHash;
+
+impl Hasher for PoseidonHash {
+    type Hash = U256;
+
+    fn hash_node(left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        poseidon_fr(&[to_field(*left), to_field(*right)]).into()
+    }
+
+    /// Domain tags are `level + 1`, leaves being level 0, so they never collide with
+    /// the zero capacity of `hash1` and `hash2`
+    fn hash_leaf(value: &Self::Hash) -> Self::Hash {
+        poseidon_fr_with_domain(Fr::one(), &[to_field(*value)]).into()
+    }
+
+    fn hash_node_at(level: usize, left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        poseidon_fr_with_domain(Fr::from(level as u64 + 1), &[to_field(*left), to_field(*right)]).into()
+    }
+
+    fn hash_to_bytes(hash: &Self::Hash) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        hash.to_big_endian(&mut bytes);
+        bytes
+    }
+}
+
+pub type CircomG1Affine = Vec<String>;
+pub type CircomG2Affine = Vec<Vec<String>>;
+
+pub fn g1_affine(g1: &CircomG1Affine) -> G1Affine {
+    G1Affine::from(G1Projective::new(
+        Fq::from_str(&g1[0]).unwrap(), 
+        Fq::from_str(&g1[1]).unwrap(), 
+        Fq::from_str(&g1[2]).unwrap()
+    ))
+}
+
+pub fn g2_affine(g2: &CircomG2Affine) -> G2Affine {
+    let x =Fq2::new(
+        Fq::from_str(&g2[0][0]).unwrap(), 
+        Fq::from_str(&g2[0][1]).unwrap(), 
+    );
+    let y = Fq2::new(
+        Fq::from_str(&g2[1][0]).unwrap(), 
+        Fq::from_str(&g2[1][1]).unwrap(), 
+    );
+    let z = Fq2::new(
+        Fq::from_str(&g2[2][0]).unwrap(), 
+        Fq::from_str(&g2[2][1]).unwrap(), 
+    );
+    G2Affine::from(G2Projective::new(x, y, z))
+}
+
+fn fq_string(value: Fq) -> String {
+    U256(value.into_repr().0).to_string()
+}
+
+/// Affine point in snarkjs's projective JSON layout, `["0", "1", "0"]` at infinity
+pub fn circom_g1(g1: &G1Affine) -> CircomG1Affine {
+    if g1.infinity {
+        return vec!["0".to_string(), "1".to_string(), "0".to_string()];
+    }
+    vec![fq_string(g1.x), fq_string(g1.y), "1".to_string()]
+}
+
+pub fn circom_g2(g2: &G2Affine) -> CircomG2Affine {
+    let pair = |v: Fq2| vec![fq_string(v.c0), fq_string(v.c1)];
+    if g2.infinity {
+        return vec![pair(Fq2::zero()), pair(Fq2::one()), pair(Fq2::zero())];
+    }
+    vec![pair(g2.x), pair(g2.y), pair(Fq2::one())]
+}
+
+/// `Fq12` as snarkjs writes `vk_alphabeta_12`, `[c0, c1]` of `[c0, c1, c2]` of `Fq2` pairs
+pub fn circom_fq12(value: &Fq12) -> Vec<Vec<Vec<String>>> {
+    [value.c0, value.c1]
+        .iter()
+        .map(|v| [v.c0, v.c1, v.c2].iter().map(|v| vec![fq_string(v.c0), fq_string(v.c1)]).collect())
+        .collect()
+}
+
+/// JSON the way snarkjs writes its files, `JSON.stringify(value, null, 1)`
+#[cfg(feature = "serde")]
+pub fn snarkjs_json<T: Serialize>(value: &T) -> String {
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b" ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+    value.serialize(&mut serializer).unwrap();
+    String::from_utf8(out).unwrap()
+}
@@ -0,0 +1,96 @@
+
+use crate::*;
+use ark_ec::PairingEngine;
+#[cfg(feature = "serde")]
+use ark_groth16::Proof;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CircomVerificationKey {
+    pub protocol: String,
+    pub curve: String,
+    #[cfg_attr(feature = "serde", serde(rename = "nPublic"))]
+    pub n_public: u64,
+    pub vk_alpha_1: CircomG1Affine,
+    pub vk_beta_2: CircomG2Affine,
+    pub vk_gamma_2: CircomG2Affine,
+    pub vk_delta_2: CircomG2Affine,
+    pub vk_alphabeta_12: Vec<Vec<Vec<String>>>,
+    #[cfg_attr(feature = "serde", serde(rename = "IC"))]
+    pub ic: Vec<CircomG1Affine>,
+}
+
+
+// impl From<CircomVerificationKey> for VerifyingKey<Bls12_381> {
+//     fn from(src: CircomVerificationKey) -> Self {
+//         let gamma_abc_g1: Vec<G1Affine> =
+//             src.ic.iter().map(g1_affine).collect();
+//         VerifyingKey {
+//             alpha_g1: g1_affine(&src.vk_alpha_1),
+//             beta_g2: g2_affine(&src.vk_beta_2),
+//             gamma_g2: g2_affine(&src.vk_gamma_2),
+//             delta_g2: g2_affine(&src.vk_delta_2),
+//             gamma_abc_g1,
+//         }
+//     }
+// }
+
+impl From<CircomVerificationKey> for VerifyingKey<Bn254> {
+    fn from(src: CircomVerificationKey) -> Self {
+        let gamma_abc_g1: Vec<G1Affine> =
+            src.ic.iter().map(g1_affine).collect();
+        VerifyingKey {
+            alpha_g1: g1_affine(&src.vk_alpha_1),
+            beta_g2: g2_affine(&src.vk_beta_2),
+            gamma_g2: g2_affine(&src.vk_gamma_2),
+            delta_g2: g2_affine(&src.vk_delta_2),
+            gamma_abc_g1,
+        }
+    }
+}
+
+impl From<VerifyingKey<Bn254>> for CircomVerificationKey {
+    fn from(src: VerifyingKey<Bn254>) -> Self {
+        CircomVerificationKey {
+            protocol: "groth16".to_string(),
+            curve: "bn128".to_string(),
+            n_public: src.gamma_abc_g1.len() as u64 - 1,
+            vk_alpha_1: circom_g1(&src.alpha_g1),
+            vk_beta_2: circom_g2(&src.beta_g2),
+            vk_gamma_2: circom_g2(&src.gamma_g2),
+            vk_delta_2: circom_g2(&src.delta_g2),
+            vk_alphabeta_12: circom_fq12(&Bn254::pairing(src.alpha_g1, src.beta_g2)),
+            ic: src.gamma_abc_g1.iter().map(circom_g1).collect(),
+        }
+    }
+}
+
+/// Checks a snarkjs proof and its public signals against a snarkjs verification key, the check `Contract::verify` makes
+#[cfg(feature = "serde")]
+pub fn verify_circom_proof(verification_key: &str, proof: &str, public_input: &str) -> Result<bool, String> {
+    let circom_verification_key: CircomVerificationKey =
+        serde_json_wasm::from_str(verification_key).map_err(|e| format!("Invalid verification key: {:?}", e))?;
+    if circom_verification_key.protocol != "groth16" {
+        return Err(format!("Unsupported protocol {}", circom_verification_key.protocol));
+    }
+    let verification_key: VerifyingKey<Bn254> = circom_verification_key.into();
+    let prepare_verifying_key = ark_groth16::prepare_verifying_key(&verification_key);
+
+    let circom_proof: CircomProof = serde_json_wasm::from_str(proof).map_err(|e| format!("Invalid proof: {:?}", e))?;
+    if circom_proof.protocol != "groth16" {
+        return Err(format!("Unsupported protocol {}", circom_proof.protocol));
+    }
+    let proof: Proof<Bn254> = circom_proof.into();
+
+    let circom_public_input: Vec<String> =
+        serde_json_wasm::from_str(public_input).map_err(|e| format!("Invalid public input: {:?}", e))?;
+    let pub_inputs = circom_public_input
+        .iter()
+        // Only canonical values, `Fr::from_str` would reduce larger ones modulo the field
+        .map(|v| {
+            let value = U256::from_dec_str(v).ok().filter(|_| !v.is_empty());
+            value.and_then(|value| Fr::try_from(value).ok()).ok_or_else(|| format!("Invalid public input {}", v))
+        })
+        .collect::<Result<Vec<Fr>, _>>()?;
+    ark_groth16::verify_proof(&prepare_verifying_key, &proof, &pub_inputs[..]).map_err(|e| e.to_string())
+}
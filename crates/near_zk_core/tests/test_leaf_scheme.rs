@@ -0,0 +1,72 @@
+use near_zk_core::*;
+
+fn u256(value: &str) -> U256 {
+    U256::from_dec_str(value).unwrap()
+}
+
+#[test]
+fn test_legacy_compatible() {
+    let question = QuestionCodec::encode("where are you from");
+    let answer = U256::from_big_endian(b"china");
+    let salt = U256::from(12345);
+
+    assert_eq!(LeafScheme::Legacy.leaf(question, answer, salt), hash2(question, answer));
+    assert_eq!(LeafScheme::Legacy.nullifier(answer, salt), hash1(answer));
+    assert_eq!(LeafScheme::Hardened { rounds: 0 }.leaf(question, answer, salt), hash2(question, answer));
+    assert_eq!(LeafScheme::default(), LeafScheme::Legacy);
+}
+
+#[test]
+fn test_hardened_leaf() {
+    let question = QuestionCodec::encode("where are you from");
+    let answer = U256::from_big_endian(b"china");
+    let salt = u256("319187461284017342158731264781237461982374619827346198234");
+    let scheme = LeafScheme::Hardened { rounds: 16 };
+
+    let key = (0..16).fold(answer, |key, _| hash2(key, salt));
+    assert_eq!(scheme.key(answer, salt), key);
+    assert_eq!(scheme.leaf(question, answer, salt), hash2(question, key));
+    assert_eq!(scheme.nullifier(answer, salt), hash1(key));
+    assert_ne!(scheme.leaf(question, answer, salt), scheme.leaf(question, answer, salt + 1));
+    assert_ne!(scheme.leaf(question, answer, salt), LeafScheme::Hardened { rounds: 15 }.leaf(question, answer, salt));
+
+    // circuits/recover_hardened_input.json
+    let mut tree = MerkleTree::<PoseidonHash>::new(3, U256::zero());
+    tree.set(0, hash2(QuestionCodec::encode("What's your favorite food"), U256::from_big_endian(b"ice cream")));
+    tree.set(1, scheme.leaf(question, answer, salt));
+    tree.set(2, hash2(QuestionCodec::encode("What's your favorite number"), U256::from_big_endian(b"666")));
+    assert_eq!(tree.root(), u256("13081020098345927189998411759578379771390204699020425707725224318678848700951"));
+}
+
+#[test]
+fn test_leaf_witness() {
+    let question = QuestionCodec::encode("where are you from");
+    let answer = U256::from_big_endian(b"china");
+    let salt = U256::from(7);
+    let scheme = LeafScheme::Hardened { rounds: 3 };
+
+    let witness = scheme.witness(question, answer, salt);
+    assert_eq!(witness.scheme(), Ok(scheme));
+    assert_eq!(witness.leaf(), Ok(scheme.leaf(question, answer, salt)));
+    assert_eq!(witness.nullifier(), Ok(scheme.nullifier(answer, salt)));
+
+    let json = serde_json::to_value(witness).unwrap();
+    assert_eq!(json["rounds"], "3");
+    assert_eq!(json["salt"], "7");
+    assert_eq!(json["question"], question.to_string());
+    assert_eq!(LeafScheme::Legacy.witness(question, answer, salt).scheme(), Ok(LeafScheme::Legacy));
+
+    // Rounds from user input beyond the circuits, or beyond u32
+    for rounds in [U256::from(MAX_HARDENED_ROUNDS + 1), U256::from(u32::MAX) + 1, U256::MAX] {
+        let witness = LeafWitness { rounds, ..witness };
+        assert_eq!(witness.scheme(), Err("Too many hardening rounds".to_string()));
+        assert!(witness.leaf().is_err() && witness.nullifier().is_err());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Too many hardening rounds")]
+fn test_rounds_limit() {
+    Question::new(QuestionCodec::encode("where are you from").to_string(), None)
+        .with_scheme(LeafScheme::Hardened { rounds: MAX_HARDENED_ROUNDS + 1 });
+}
@@ -0,0 +1,133 @@
+use near_zk_core::*;
+
+fn sample_tree(depth: usize) -> MerkleTree<PoseidonHash> {
+    let mut tree = MerkleTree::<PoseidonHash>::new(depth, U256::zero());
+    tree.set_range(0, (1..=tree.num_leaves() as u64).map(U256::from));
+    tree
+}
+
+#[test]
+fn test_multi_proof() {
+    let tree = sample_tree(4);
+    let leaves = tree.leaves().to_vec();
+
+    for indices in [vec![0], vec![7], vec![0, 1], vec![2, 5, 6], vec![0, 1, 2, 3, 4, 5, 6, 7]] {
+        let proof = tree.multi_proof(&indices).unwrap();
+        let hashes = indices.iter().map(|&i| leaves[i]).collect::<Vec<_>>();
+        assert_eq!(proof.root(&hashes), Some(tree.root()));
+        assert!(tree.verify_multi(&hashes, &proof));
+    }
+
+    // Shared siblings are not repeated
+    assert_eq!(tree.multi_proof(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap().hashes.len(), 0);
+    assert_eq!(tree.multi_proof(&[0, 1]).unwrap().hashes.len(), 2);
+    assert_eq!(tree.multi_proof(&[7]).unwrap().hashes.len(), 3);
+}
+
+#[test]
+fn test_multi_proof_rejects() {
+    let tree = sample_tree(4);
+    let leaves = tree.leaves().to_vec();
+
+    assert!(tree.multi_proof(&[]).is_none());
+    assert!(tree.multi_proof(&[8]).is_none());
+
+    let proof = tree.multi_proof(&[1, 4]).unwrap();
+    assert!(!proof.verify(&[leaves[1], leaves[5]], &tree.root()));
+    assert!(proof.root(&[leaves[1]]).is_none());
+
+    let mut truncated = proof.clone();
+    truncated.hashes.pop();
+    assert!(truncated.root(&[leaves[1], leaves[4]]).is_none());
+
+    let mut padded = proof.clone();
+    padded.hashes.push(U256::zero());
+    assert!(padded.root(&[leaves[1], leaves[4]]).is_none());
+
+    // Depths from a decoded proof that would overflow the leaf count
+    for depth in [65, 1 << 32 | 5, usize::MAX] {
+        let oversized = MultiProof { depth, ..proof.clone() };
+        assert!(oversized.root(&[leaves[1], leaves[4]]).is_none());
+    }
+}
+
+#[test]
+fn test_proof_serde() {
+    let tree = sample_tree(3);
+    let proof = tree.proof(2).unwrap();
+    assert!(proof.verify(tree.leaves()[2], &tree.root()));
+    assert!(!proof.verify(tree.leaves()[1], &tree.root()));
+
+    let json = serde_json::to_value(&proof).unwrap();
+    assert_eq!(json["pathIndices"], serde_json::json!(["0", "1"]));
+    assert_eq!(json["siblings"][0], tree.leaves()[3].to_string());
+
+    let decoded = serde_json::from_value(json).unwrap();
+    assert!(proof == decoded);
+
+    let json = serde_json::to_string(&tree).unwrap();
+    let decoded: MerkleTree<PoseidonHash> = serde_json::from_str(&json).unwrap();
+    assert!(decoded == tree);
+}
+
+#[test]
+fn test_domain_separated_tree() {
+    let mut tree = MerkleTree::<PoseidonHash>::new_with_mode(3, U256::zero(), TreeMode::DomainSeparated);
+    let mut legacy = MerkleTree::<PoseidonHash>::new(3, U256::zero());
+    assert_eq!(tree.empty_leaf(), PoseidonHash::hash_leaf(&U256::zero()));
+    assert_ne!(tree.root(), legacy.root());
+
+    tree.set(1, U256::from(42));
+    legacy.set(1, U256::from(42));
+    assert_eq!(tree.leaves()[1], PoseidonHash::hash_leaf(&U256::from(42)));
+
+    let proof = tree.proof(1).unwrap();
+    assert!(tree.verify(U256::from(42), &proof));
+    assert!(!legacy.verify(U256::from(42), &proof));
+    assert_eq!(proof.root_with_mode(TreeMode::DomainSeparated, U256::from(42)), tree.root());
+    assert_ne!(proof.root(U256::from(42)), tree.root());
+
+    let multi = tree.multi_proof(&[1, 2]).unwrap();
+    assert!(tree.verify_multi(&[U256::from(42), U256::zero()], &multi));
+
+    // Proofs verify off-chain in the mode of their tree, after a round trip through JSON
+    let proof: Proof<PoseidonHash> = serde_json::from_str(&serde_json::to_string(&proof).unwrap()).unwrap();
+    assert!(proof.verify_with_mode(TreeMode::DomainSeparated, U256::from(42), &tree.root()));
+    assert!(!proof.verify(U256::from(42), &tree.root()));
+    let multi: MultiProof<PoseidonHash> = serde_json::from_str(&serde_json::to_string(&multi).unwrap()).unwrap();
+    assert!(multi.verify_with_mode(TreeMode::DomainSeparated, &[U256::from(42), U256::zero()], &tree.root()));
+    assert!(!multi.verify(&[U256::from(42), U256::zero()], &tree.root()));
+
+    tree.move_leaf(1, 0);
+    assert!(tree.verify(U256::from(42), &tree.proof(0).unwrap()));
+    tree.reset(0);
+    assert_eq!(tree.root(), MerkleTree::<PoseidonHash>::new_with_mode(3, U256::zero(), TreeMode::DomainSeparated).root());
+}
+
+#[test]
+fn test_secondary_commitment() {
+    let zero = H256::default();
+    assert_eq!(
+        Keccak256Hash::hash_node(&zero, &zero).to_hex(),
+        "0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"
+    );
+    assert_eq!(
+        Sha256Hash::hash_node(&zero, &zero).to_hex(),
+        "0xf5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"
+    );
+
+    for kind in [ByteHashKind::Keccak256, ByteHashKind::Sha256] {
+        let mut tree = MerkleTree::<PoseidonHash>::new(3, U256::zero()).with_secondary(kind);
+        tree.set(0, U256::from(1));
+        tree.set(2, U256::from(3));
+        tree.move_leaf(2, 1);
+
+        let bytes = tree.leaves().iter().map(|v| H256(PoseidonHash::hash_to_bytes(v))).collect::<Vec<_>>();
+        let mut expected = SecondaryTree::new(kind, 3, H256::default(), TreeMode::Legacy);
+        expected.set_range(0, &bytes);
+        assert_eq!(tree.secondary().unwrap().root(), expected.root());
+
+        tree.clear();
+        assert_eq!(tree.secondary().unwrap().root(), SecondaryTree::new(kind, 3, H256::default(), TreeMode::Legacy).root());
+    }
+}
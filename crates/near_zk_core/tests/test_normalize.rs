@@ -0,0 +1,42 @@
+use near_zk_core::*;
+
+#[test]
+fn test_profiles() {
+    for answer in ["Ice cream", "ice cream ", "ICE CREAM", "  ice\tcream", "ｉｃｅ ｃｒｅａｍ"] {
+        assert_eq!(NormalizationProfile::Standard.normalize(answer), "ice cream");
+    }
+    assert_eq!(NormalizationProfile::Standard.normalize("Straße"), "strasse");
+    assert_eq!(NormalizationProfile::Standard.normalize("Café"), NormalizationProfile::Standard.normalize("Cafe\u{301}"));
+    assert_eq!(NormalizationProfile::Standard.normalize("St. John's"), "st. john's");
+
+    assert_eq!(NormalizationProfile::Loose.normalize("St. John's"), "st johns");
+    assert_eq!(NormalizationProfile::Loose.normalize("ice-cream!"), "icecream");
+    assert_eq!(NormalizationProfile::Loose.normalize("北京, 中国"), "北京 中国");
+
+    assert_eq!(NormalizationProfile::Raw.normalize(" Ice cream "), " Ice cream ");
+    assert_eq!(NormalizationProfile::default(), NormalizationProfile::Raw);
+}
+
+#[test]
+fn test_custom_rules() {
+    let rules = NormalizationRules { collapse_whitespace: true, ..Default::default() };
+    assert_eq!(rules.apply("  Ice   Cream "), "Ice Cream");
+
+    let rules = NormalizationRules { strip_punctuation: true, ..Default::default() };
+    assert_eq!(rules.apply("A.B, c"), "AB c");
+}
+
+#[test]
+fn test_encode_answer() {
+    assert_eq!(encode_answer("ice cream", NormalizationProfile::Raw), U256::from_big_endian(b"ice cream"));
+    assert_eq!(encode_answer(" Ice Cream", NormalizationProfile::Standard), U256::from_big_endian(b"ice cream"));
+
+    let question = QuestionCodec::encode("What's your favorite food");
+    assert_eq!(
+        hash2(question, encode_answer("ICE  CREAM", NormalizationProfile::Standard)),
+        hash2(question, U256::from_big_endian(b"ice cream"))
+    );
+
+    let long = "The small bakery on the corner of Main Street";
+    assert_eq!(encode_answer(long, NormalizationProfile::Loose), poseidon_bytes(long.to_lowercase().as_bytes()));
+}
@@ -0,0 +1,158 @@
+use near_zk_core::*;
+
+fn u256(value: &str) -> U256 {
+    U256::from_dec_str(value).unwrap()
+}
+
+fn inputs(values: &[u64]) -> Vec<U256> {
+    values.iter().cloned().map(U256::from).collect()
+}
+
+#[test]
+fn test_poseidon_reference_vectors() {
+    // circomlibjs test vectors
+    assert_eq!(
+        hash1(U256::from(1)),
+        u256("18586133768512220936620570745912940619677854269274689475585506675881198879027")
+    );
+    assert_eq!(
+        hash2(U256::from(1), U256::from(2)),
+        u256("7853200120776062878684798364095072458815029376092732009249414926327459813530")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 3, 4])),
+        u256("18821383157269793795438455681495246036402687001665670618754263018637548127333")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 0, 0, 0])),
+        u256("1018317224307729531995786483840663576608797660851238720571059489595066344487")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[3, 4, 0, 0, 0])),
+        u256("5811595552068139067952687508729883632420015185677766880877743348592482390548")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 0, 0, 0, 0])),
+        u256("15336558801450556532856248569924170992202208561737609669134139141992924267169")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[3, 4, 0, 0, 0, 0])),
+        u256("12263118664590987767234828103155242843640892839966517009184493198782366909018")
+    );
+
+    // go-iden3-crypto test vectors, which match circomlib for every width
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 3, 4, 5, 6])),
+        u256("20400040500897583745843009878988256314335038853985262692600694741116813247201")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0, 0, 0])),
+        u256("5540388656744764564518487011617040650780060800286365721923524861648744699539")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14])),
+        u256("8354478399926161176778659061636406690034081872658507739535256090879947077494")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0, 0, 0, 0, 0])),
+        u256("11882816200654282475720830292386643970958445617880627439994635298904836126497")
+    );
+    assert_eq!(
+        poseidon(&inputs(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])),
+        u256("9989051620750914585850546081941653841776809718687451684622678807385399211877")
+    );
+}
+
+#[test]
+fn test_poseidon_matches_circuit_inputs() {
+    // Root of `circuits/recover_input.json`, produced by circomlib
+    let leaf = hash2(u256("10401886238039751930132277276046594434821997"), u256("426953502305"));
+    let proof = Proof(vec![
+        Branch::<PoseidonHash>::Right(u256("5976510632022265170641079443789669694937857272158269197258399543173721084850")),
+        Branch::Left(u256("8465561691814615278226138070938400032659491311941445396550366728308578331946")),
+    ]);
+    assert_eq!(
+        proof.root(leaf),
+        u256("18171122834797905172797652986383447716767351806060852067471197098378314307122")
+    );
+}
+
+#[test]
+fn test_poseidon_arities() {
+    for n in 1..=POSEIDON_MAX_INPUTS {
+        let params = poseidon_params(n);
+        assert_eq!(params.t, n + 1);
+        assert_eq!(params.round_constants().len(), params.rounds_f + params.rounds_p);
+        assert_eq!(params.mds_rows().len(), n + 1);
+        assert_eq!(params.m.len(), (n + 1) * (n + 1));
+        assert_ne!(poseidon(&inputs(&vec![1; n])), poseidon(&inputs(&vec![2; n])));
+    }
+}
+
+#[test]
+fn test_poseidon_tables_match_grain() {
+    // The tables written by build.rs are the parameters of the Grain LFSR
+    for n in 1..=POSEIDON_MAX_INPUTS {
+        let params = poseidon_params(n);
+        let generated = PoseidonParams::generate(params.t, params.rounds_f, params.rounds_p);
+        assert_eq!(params.c, generated.c);
+        assert_eq!(params.m, generated.m);
+    }
+    assert_eq!(poseidon_params(1).rounds_p, 56);
+    assert_eq!(poseidon_params(16).rounds_p, 68);
+}
+
+#[test]
+fn test_field_conversions() {
+    use ark_bn254::Fr;
+    use std::str::FromStr;
+
+    let value = u256("7853200120776062878684798364095072458815029376092732009249414926327459813530");
+    let fr = Fr::try_from(value).unwrap();
+    assert_eq!(fr, Fr::from_str(&value.to_string()).unwrap());
+    assert_eq!(U256::from(fr), value);
+    assert_eq!(U256::from(poseidon_fr(&[Fr::from(1u64), Fr::from(2u64)])), value);
+
+    let modulus = u256("21888242871839275222246405745257275088548364400416034343698204186575808495617");
+    assert!(Fr::try_from(modulus).is_err());
+    assert!(Fr::try_from(modulus - 1).is_ok());
+}
+
+#[test]
+fn test_poseidon_sponge() {
+    use ark_bn254::Fr;
+    use ark_ff::Zero;
+
+    let packed = pack_bytes(b"ab");
+    assert_eq!(packed, vec![Fr::from(0x6162u64), Fr::from(2u64)]);
+    assert_eq!(pack_bytes(&[1; 62]).len(), 3);
+    assert_eq!(pack_bytes(&[1; 63]).len(), 4);
+    assert_eq!(pack_bytes(b""), vec![Fr::zero()]);
+
+    // One block is a single permutation with capacity 2^64 + nOuts - 1
+    let mut state = [Fr::from(u64::MAX) + Fr::from(3u64), packed[0], packed[1]];
+    poseidon_params(2).permute(&mut state);
+    let mut sponge = PoseidonSponge::new(3);
+    sponge.absorb_bytes(b"ab");
+    let outputs = sponge.squeeze();
+    assert_eq!(outputs[..2], state[1..]);
+    poseidon_params(2).permute(&mut state);
+    assert_eq!(outputs[2], state[1]);
+
+    let mut state = [Fr::from(u64::MAX) + Fr::from(1u64), packed[0], packed[1]];
+    poseidon_params(2).permute(&mut state);
+    assert_eq!(poseidon_bytes(b"ab"), U256::from(state[1]));
+
+    // Absorbing in pieces is the same as absorbing at once
+    let message = "What was the name of the street you grew up on, and which house number?";
+    let mut sponge = PoseidonSponge::new(1);
+    let packed = pack_bytes(message.as_bytes());
+    sponge.absorb(&packed[..1]);
+    sponge.absorb(&packed[1..]);
+    assert_eq!(U256::from(sponge.squeeze()[0]), poseidon_bytes(message.as_bytes()));
+
+    // Length padding separates messages that pack to the same chunks
+    assert_ne!(poseidon_bytes(b"a"), poseidon_bytes(b"a\0"));
+    assert_ne!(poseidon_bytes(b""), poseidon_bytes(b"\0"));
+    assert_ne!(poseidon_bytes(&[7; 31]), poseidon_bytes(&[7; 32]));
+}
@@ -0,0 +1,54 @@
+use near_zk_core::*;
+
+#[test]
+fn test_inline_questions() {
+    let question = "What's your favorite food";
+    let value = QuestionCodec::encode(question);
+    assert_eq!(value, U256::from_big_endian(question.as_bytes()));
+    assert_eq!(QuestionCodec::decode(value).as_deref(), Some(question));
+
+    let unicode = "你最喜欢的食物是什么";
+    assert_eq!(unicode.len(), 30);
+    assert_eq!(QuestionCodec::encode(unicode), U256::from_big_endian(unicode.as_bytes()));
+    assert_eq!(QuestionCodec::decode(QuestionCodec::encode(unicode)).as_deref(), Some(unicode));
+
+    assert_eq!(QuestionCodec::decode(U256::zero()), None);
+    assert_eq!(QuestionCodec::decode(U256::from_big_endian(&[0xff, 0xfe])), None);
+}
+
+#[test]
+fn test_long_questions() {
+    let question = "What was the name of the street you grew up on?";
+    assert!(question.len() > INLINE_QUESTION_BYTES);
+    let value = QuestionCodec::encode(question);
+    assert_eq!(value, poseidon_bytes(question.as_bytes()));
+    assert_eq!(QuestionCodec::decode(value), None);
+
+    // 32 bytes no longer fit in one field element
+    let question = "a".repeat(INLINE_QUESTION_BYTES + 1);
+    assert_eq!(QuestionCodec::encode(&question), poseidon_bytes(question.as_bytes()));
+    assert_eq!(QuestionCodec::encode(&question[1..]), U256::from_big_endian(&question.as_bytes()[1..]));
+
+    // NUL bytes would be lost by inline decoding
+    assert_eq!(QuestionCodec::encode("\0a"), poseidon_bytes(b"\0a"));
+}
+
+#[test]
+fn test_question_records() {
+    let text = "What was the name of your first school teacher?".to_string();
+    let value = QuestionCodec::encode(&text).to_string();
+    assert!(QuestionCodec::matches(&text, &value));
+
+    let question = Question::new(value.clone(), Some(text.clone()));
+    assert_eq!(question.readable_text(), Some(text));
+    assert_eq!(Question::new(value, None).readable_text(), None);
+
+    let value = QuestionCodec::encode("where are you from").to_string();
+    assert_eq!(Question::new(value, None).readable_text().as_deref(), Some("where are you from"));
+}
+
+#[test]
+#[should_panic(expected = "Question text does not match")]
+fn test_question_text_mismatch() {
+    Question::new(QuestionCodec::encode("where are you from").to_string(), Some("where were you born".to_string()));
+}
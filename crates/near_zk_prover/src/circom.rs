@@ -0,0 +1,339 @@
+use crate::*;
+
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Result};
+
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_bn254::{FqParameters, FrParameters};
+use ark_ff::{FftParameters, FpParameters, FromBytes, PrimeField, UniformRand, Zero};
+use ark_groth16::ProvingKey;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError, Variable,
+};
+use ark_std::rand::{CryptoRng, RngCore};
+
+/// Sparse row of a constraint matrix, `(wire, coefficient)` pairs
+pub type Row = Vec<(usize, Fr)>;
+
+fn invalid<T>(message: &str) -> Result<T> {
+    Err(Error::new(ErrorKind::InvalidData, message))
+}
+
+/// Sectioned binary file of the iden3 formats: magic, version, then `(type, size, data)` sections
+struct BinFile<'a> {
+    sections: HashMap<u32, &'a [u8]>,
+}
+
+impl<'a> BinFile<'a> {
+    fn parse(bytes: &'a [u8], magic: &[u8; 4]) -> Result<Self> {
+        let mut reader = Reader(bytes);
+        if reader.bytes(4)? != magic {
+            return invalid("Invalid magic");
+        }
+        reader.u32()?;
+        let mut sections = HashMap::new();
+        for _ in 0..reader.u32()? {
+            let kind = reader.u32()?;
+            let size = reader.u64()? as usize;
+            sections.entry(kind).or_insert(reader.bytes(size)?);
+        }
+        Ok(Self { sections })
+    }
+
+    fn section(&self, kind: u32) -> Result<Reader<'a>> {
+        match self.sections.get(&kind) {
+            Some(data) => Ok(Reader(data)),
+            None => invalid("Missing section"),
+        }
+    }
+}
+
+/// Little endian reader over a byte slice
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "Truncated file"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn bigint(&mut self) -> Result<BigInteger256> {
+        BigInteger256::read(self.bytes(32)?)
+    }
+
+    /// Field size header of `.r1cs` and `.wtns` files, only BN254 is supported
+    fn field_header(&mut self) -> Result<()> {
+        let n8 = self.u32()?;
+        if n8 != 32 || self.bigint()? != FrParameters::MODULUS {
+            return invalid("Only the BN254 scalar field is supported");
+        }
+        Ok(())
+    }
+
+    /// Field element as a plain integer
+    fn fr(&mut self) -> Result<Fr> {
+        Fr::from_repr(self.bigint()?).map_or_else(|| invalid("Field element out of range"), Ok)
+    }
+
+    /// Field element in Montgomery form multiplied by R, as snarkjs stores coefficients
+    fn fr_montgomery2(&mut self) -> Result<Fr> {
+        Ok(Fr::new(Fr::new(self.bigint()?).into_repr()))
+    }
+
+    /// Base field element in Montgomery form
+    fn fq(&mut self) -> Result<Fq> {
+        Ok(Fq::new(self.bigint()?))
+    }
+
+    fn g1(&mut self) -> Result<G1Affine> {
+        let (x, y) = (self.fq()?, self.fq()?);
+        Ok(G1Affine::new(x, y, x.is_zero() && y.is_zero()))
+    }
+
+    fn g2(&mut self) -> Result<G2Affine> {
+        let x = Fq2::new(self.fq()?, self.fq()?);
+        let y = Fq2::new(self.fq()?, self.fq()?);
+        Ok(G2Affine::new(x, y, x.is_zero() && y.is_zero()))
+    }
+
+    fn row(&mut self) -> Result<Row> {
+        (0..self.u32()?).map(|_| Ok((self.u32()? as usize, self.fr()?))).collect()
+    }
+}
+
+fn evaluate(row: &[(usize, Fr)], witness: &[Fr]) -> Fr {
+    row.iter().map(|(wire, coeff)| *coeff * witness[*wire]).sum()
+}
+
+/// Constraint system of a circom `.r1cs` file
+#[derive(Clone, Debug)]
+pub struct R1csFile {
+    pub n_wires: usize,
+    pub n_pub_out: usize,
+    pub n_pub_in: usize,
+    pub n_prv_in: usize,
+    /// `(a, b, c)` rows of each constraint `a * b = c`
+    pub constraints: Vec<(Row, Row, Row)>,
+}
+
+impl R1csFile {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let file = BinFile::parse(bytes, b"r1cs")?;
+        let mut header = file.section(1)?;
+        header.field_header()?;
+        let n_wires = header.u32()? as usize;
+        let n_pub_out = header.u32()? as usize;
+        let n_pub_in = header.u32()? as usize;
+        let n_prv_in = header.u32()? as usize;
+        header.u64()?;
+        let n_constraints = header.u32()?;
+
+        let mut section = file.section(2)?;
+        let constraints = (0..n_constraints)
+            .map(|_| Ok((section.row()?, section.row()?, section.row()?)))
+            .collect::<Result<_>>()?;
+        Ok(Self { n_wires, n_pub_out, n_pub_in, n_prv_in, constraints })
+    }
+
+    /// Number of public signals, outputs then public inputs
+    pub fn n_public(&self) -> usize {
+        self.n_pub_out + self.n_pub_in
+    }
+
+    pub fn is_satisfied(&self, witness: &[Fr]) -> bool {
+        witness.len() == self.n_wires
+            && self.constraints.iter().all(|(a, b, c)| evaluate(a, witness) * evaluate(b, witness) == evaluate(c, witness))
+    }
+}
+
+/// Witness of a circom `.wtns` file, starting with the constant `1` and the public signals
+pub fn parse_witness(bytes: &[u8]) -> Result<Vec<Fr>> {
+    let file = BinFile::parse(bytes, b"wtns")?;
+    let mut header = file.section(1)?;
+    header.field_header()?;
+    let n_witness = header.u32()?;
+    let mut section = file.section(2)?;
+    (0..n_witness).map(|_| section.fr()).collect()
+}
+
+/// Public signals of a witness, as passed to `Contract::verify`
+pub fn public_signals(witness: &[Fr], n_public: usize) -> Vec<U256> {
+    witness[1..=n_public].iter().map(|v| U256::from(*v)).collect()
+}
+
+/// Circom circuit as an arkworks constraint system, for proving with keys generated by arkworks
+#[derive(Clone)]
+pub struct CircomCircuit {
+    pub r1cs: R1csFile,
+    /// `None` when generating parameters
+    pub witness: Option<Vec<Fr>>,
+}
+
+impl ConstraintSynthesizer<Fr> for CircomCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> std::result::Result<(), SynthesisError> {
+        let witness = self.witness.as_ref();
+        let n_public = self.r1cs.n_public();
+        let mut wires = vec![Variable::One];
+        for i in 1..self.r1cs.n_wires {
+            let value = || witness.map(|v| v[i]).ok_or(SynthesisError::AssignmentMissing);
+            wires.push(if i <= n_public { cs.new_input_variable(value)? } else { cs.new_witness_variable(value)? });
+        }
+        let lc = |row: &Row| row.iter().fold(LinearCombination::zero(), |lc, (wire, coeff)| lc + (*coeff, wires[*wire]));
+        for (a, b, c) in &self.r1cs.constraints {
+            cs.enforce_constraint(lc(a), lc(b), lc(c))?;
+        }
+        Ok(())
+    }
+}
+
+/// Groth16 proving key of a snarkjs `.zkey` file, with the `A` and `B` matrices it stores
+#[derive(Clone, Debug)]
+pub struct Zkey {
+    pub proving_key: ProvingKey<Bn254>,
+    pub n_public: usize,
+    pub domain_size: usize,
+    pub a: Vec<Row>,
+    pub b: Vec<Row>,
+}
+
+impl Zkey {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let file = BinFile::parse(bytes, b"zkey")?;
+        if file.section(1)?.u32()? != 1 {
+            return invalid("Only groth16 keys are supported");
+        }
+
+        let mut header = file.section(2)?;
+        if header.u32()? != 32 || header.bigint()? != FqParameters::MODULUS {
+            return invalid("Only BN254 keys are supported");
+        }
+        if header.u32()? != 32 || header.bigint()? != FrParameters::MODULUS {
+            return invalid("Only BN254 keys are supported");
+        }
+        let n_vars = header.u32()? as usize;
+        let n_public = header.u32()? as usize;
+        let domain_size = header.u32()? as usize;
+        let alpha_g1 = header.g1()?;
+        let beta_g1 = header.g1()?;
+        let beta_g2 = header.g2()?;
+        let gamma_g2 = header.g2()?;
+        let delta_g1 = header.g1()?;
+        let delta_g2 = header.g2()?;
+
+        // Checked before allocating anything of these sizes, snarkjs fits each section to its points
+        if !domain_size.is_power_of_two() || domain_size > 1 << (FrParameters::TWO_ADICITY - 1) {
+            return invalid("Invalid domain size");
+        }
+        let n_private = n_vars.checked_sub(n_public + 1).map_or_else(|| invalid("Fewer variables than public signals"), Ok)?;
+        let points = |kind: u32, len: usize, point_size: usize| -> Result<Reader> {
+            let section = file.section(kind)?;
+            if len.checked_mul(point_size) != Some(section.0.len()) {
+                return invalid("Section size differs from the header");
+            }
+            Ok(section)
+        };
+        let g1_points = |kind: u32, len: usize| -> Result<Vec<G1Affine>> {
+            let mut section = points(kind, len, 64)?;
+            (0..len).map(|_| section.g1()).collect()
+        };
+        let gamma_abc_g1 = g1_points(3, n_public + 1)?;
+        let a_query = g1_points(5, n_vars)?;
+        let b_g1_query = g1_points(6, n_vars)?;
+        let mut section = points(7, n_vars, 128)?;
+        let b_g2_query = (0..n_vars).map(|_| section.g2()).collect::<Result<_>>()?;
+        let l_query = g1_points(8, n_private)?;
+        let h_query = g1_points(9, domain_size)?;
+
+        let mut a = vec![Row::new(); domain_size];
+        let mut b = vec![Row::new(); domain_size];
+        let mut section = file.section(4)?;
+        for _ in 0..section.u32()? {
+            let matrix = section.u32()?;
+            let constraint = section.u32()? as usize;
+            let signal = section.u32()? as usize;
+            let value = section.fr_montgomery2()?;
+            if signal >= n_vars {
+                return invalid("Signal outside the witness");
+            }
+            let rows = if matrix == 0 { &mut a } else { &mut b };
+            match rows.get_mut(constraint) {
+                Some(row) => row.push((signal, value)),
+                None => return invalid("Constraint outside the domain"),
+            }
+        }
+
+        let vk = VerifyingKey { alpha_g1, beta_g2, gamma_g2, delta_g2, gamma_abc_g1 };
+        let proving_key = ProvingKey { vk, beta_g1, delta_g1, a_query, b_g1_query, b_g2_query, h_query, l_query };
+        Ok(Self { proving_key, n_public, domain_size, a, b })
+    }
+
+    pub fn verifying_key(&self) -> &VerifyingKey<Bn254> {
+        &self.proving_key.vk
+    }
+
+    pub fn prove<R: RngCore + CryptoRng>(&self, witness: &[Fr], rng: &mut R) -> Result<Proof<Bn254>> {
+        self.prove_with_randomness(witness, Fr::rand(rng), Fr::rand(rng))
+    }
+
+    /// Groth16 prover with snarkjs's quotient: `A * B - C` is evaluated on the odd coset of the
+    /// domain instead of dividing by the vanishing polynomial, which the `.zkey` `H` points expect
+    pub fn prove_with_randomness(&self, witness: &[Fr], r: Fr, s: Fr) -> Result<Proof<Bn254>> {
+        let pk = &self.proving_key;
+        if witness.len() != pk.a_query.len() {
+            return invalid("Witness length differs from the key");
+        }
+        let h = self.quotient(witness);
+        let scalars = |values: &[Fr]| values.iter().map(|v| v.into_repr()).collect::<Vec<_>>();
+        let witness_scalars = scalars(witness);
+
+        let mut g_a = VariableBaseMSM::multi_scalar_mul(&pk.a_query, &witness_scalars);
+        g_a.add_assign_mixed(&pk.vk.alpha_g1);
+        g_a += &pk.delta_g1.mul(r);
+
+        let mut g1_b = VariableBaseMSM::multi_scalar_mul(&pk.b_g1_query, &witness_scalars);
+        g1_b.add_assign_mixed(&pk.beta_g1);
+        g1_b += &pk.delta_g1.mul(s);
+
+        let mut g2_b = VariableBaseMSM::multi_scalar_mul(&pk.b_g2_query, &witness_scalars);
+        g2_b.add_assign_mixed(&pk.vk.beta_g2);
+        g2_b += &pk.vk.delta_g2.mul(s);
+
+        let mut g_c = VariableBaseMSM::multi_scalar_mul(&pk.l_query, &witness_scalars[self.n_public + 1..]);
+        g_c += &VariableBaseMSM::multi_scalar_mul(&pk.h_query, &scalars(&h));
+        g_c += &g_a.mul(s.into_repr());
+        g_c += &g1_b.mul(r.into_repr());
+        g_c -= &pk.delta_g1.mul(r * s);
+
+        Ok(Proof { a: g_a.into_affine(), b: g2_b.into_affine(), c: g_c.into_affine() })
+    }
+
+    fn quotient(&self, witness: &[Fr]) -> Vec<Fr> {
+        let domain = Radix2EvaluationDomain::<Fr>::new(self.domain_size).unwrap();
+        let mut a = self.a.iter().map(|row| evaluate(row, witness)).collect::<Vec<_>>();
+        let mut b = self.b.iter().map(|row| evaluate(row, witness)).collect::<Vec<_>>();
+        let mut c = a.iter().zip(&b).map(|(a, b)| *a * b).collect::<Vec<_>>();
+
+        let shift = Radix2EvaluationDomain::<Fr>::new(2 * self.domain_size).unwrap().element(1);
+        for values in [&mut a, &mut b, &mut c] {
+            domain.ifft_in_place(values);
+            Radix2EvaluationDomain::distribute_powers(values, shift);
+            domain.fft_in_place(values);
+        }
+        a.iter().zip(&b).zip(&c).map(|((a, b), c)| *a * b - c).collect()
+    }
+}
+
@@ -0,0 +1,21 @@
+//! Native Groth16 proving of the recover and update circuits, circom file loading, witness calculation
+//! and decoding of the contract state from an RPC dump. Off-chain code, the contract only verifies.
+
+use std::iter::once;
+
+use ark_groth16::{VerifyingKey, Proof};
+use ark_bn254::{Fr, Fq, Fq2, G1Affine, G2Affine, Bn254};
+use ark_ff::biginteger::BigInteger256;
+use serde::Serialize;
+use near_zk_core::*;
+use near_zk_demo1::Contract;
+
+mod prover;
+mod circom;
+mod witness;
+mod state;
+
+pub use prover::*;
+pub use circom::*;
+pub use witness::*;
+pub use state::*;
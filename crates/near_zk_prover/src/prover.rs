@@ -0,0 +1,297 @@
+use crate::*;
+use near_zk_core::Proof as MerkleProof;
+
+use ark_ff::{Field, One, Zero};
+use ark_groth16::ProvingKey;
+use ark_relations::lc;
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError, Variable,
+};
+use ark_std::rand::{CryptoRng, RngCore};
+
+type Result<T> = std::result::Result<T, SynthesisError>;
+
+/// Field element in a constraint system, a linear combination of variables with its value.
+/// The value is `None` while generating parameters.
+#[derive(Clone)]
+pub struct Num {
+    pub lc: LinearCombination<Fr>,
+    pub value: Option<Fr>,
+}
+
+impl Num {
+    pub fn constant(value: Fr) -> Self {
+        Self { lc: lc!() + (value, Variable::One), value: Some(value) }
+    }
+
+    pub fn input(cs: &ConstraintSystemRef<Fr>, value: Option<U256>) -> Result<Self> {
+        let value = value.map(to_field);
+        let variable = cs.new_input_variable(|| value.ok_or(SynthesisError::AssignmentMissing))?;
+        Ok(Self { lc: variable.into(), value })
+    }
+
+    pub fn witness(cs: &ConstraintSystemRef<Fr>, value: Option<U256>) -> Result<Self> {
+        Self::witness_fr(cs, value.map(to_field))
+    }
+
+    fn witness_fr(cs: &ConstraintSystemRef<Fr>, value: Option<Fr>) -> Result<Self> {
+        let variable = cs.new_witness_variable(|| value.ok_or(SynthesisError::AssignmentMissing))?;
+        Ok(Self { lc: variable.into(), value })
+    }
+
+    pub fn add(&self, other: &Num) -> Num {
+        Num { lc: &self.lc + &other.lc, value: self.value.zip(other.value).map(|(a, b)| a + b) }
+    }
+
+    pub fn sub(&self, other: &Num) -> Num {
+        Num { lc: &self.lc - &other.lc, value: self.value.zip(other.value).map(|(a, b)| a - b) }
+    }
+
+    pub fn scale(&self, coeff: Fr) -> Num {
+        Num { lc: &self.lc * coeff, value: self.value.map(|v| v * coeff) }
+    }
+
+    /// Allocates the product, one constraint
+    pub fn mul(&self, cs: &ConstraintSystemRef<Fr>, other: &Num) -> Result<Num> {
+        let product = Self::witness_fr(cs, self.value.zip(other.value).map(|(a, b)| a * b))?;
+        cs.enforce_constraint(self.lc.clone(), other.lc.clone(), product.lc.clone())?;
+        Ok(product)
+    }
+
+    pub fn enforce_equal(&self, cs: &ConstraintSystemRef<Fr>, other: &Num) -> Result<()> {
+        cs.enforce_constraint(self.lc.clone() - &other.lc, lc!() + Variable::One, lc!())
+    }
+
+    pub fn enforce_bit(&self, cs: &ConstraintSystemRef<Fr>) -> Result<()> {
+        cs.enforce_constraint(self.lc.clone(), lc!() + Variable::One - &self.lc, lc!())
+    }
+
+    /// `1` if equal to `other`, otherwise `0`, like circomlib's `IsEqual`, two constraints
+    pub fn is_equal(&self, cs: &ConstraintSystemRef<Fr>, other: &Num) -> Result<Num> {
+        let diff = self.sub(other);
+        let inverse = Self::witness_fr(cs, diff.value.map(|v| v.inverse().unwrap_or_else(Fr::zero)))?;
+        let out = Self::witness_fr(cs, diff.value.map(|v| if v.is_zero() { Fr::one() } else { Fr::zero() }))?;
+        cs.enforce_constraint(diff.lc.clone(), inverse.lc, lc!() + Variable::One - &out.lc)?;
+        cs.enforce_constraint(diff.lc, out.lc.clone(), lc!())?;
+        Ok(out)
+    }
+
+    /// x^5, three constraints
+    fn pow5(&self, cs: &ConstraintSystemRef<Fr>) -> Result<Num> {
+        let x2 = self.mul(cs, self)?;
+        let x4 = x2.mul(cs, &x2)?;
+        x4.mul(cs, self)
+    }
+}
+
+/// Poseidon gadget, the same permutation as `PoseidonParams::permute` and circomlib's `Poseidon(n)`
+pub fn poseidon_gadget(cs: &ConstraintSystemRef<Fr>, inputs: &[Num]) -> Result<Num> {
+    poseidon_gadget_with_domain(cs, Num::constant(Fr::zero()), inputs)
+}
+
+/// Poseidon gadget with the capacity element set to `domain`, like circomlib's `PoseidonEx(n, 1)`
+pub fn poseidon_gadget_with_domain(cs: &ConstraintSystemRef<Fr>, domain: Num, inputs: &[Num]) -> Result<Num> {
+    let params = poseidon_params(inputs.len());
+    let partial = params.rounds_f / 2..params.rounds_f / 2 + params.rounds_p;
+    let mut state = once(domain).chain(inputs.iter().cloned()).collect::<Vec<_>>();
+    for (i, constants) in params.round_constants().enumerate() {
+        state = state.iter().zip(constants).map(|(v, c)| v.add(&Num::constant(*c))).collect();
+        if partial.contains(&i) {
+            state[0] = state[0].pow5(cs)?;
+        } else {
+            state = state.iter().map(|v| v.pow5(cs)).collect::<Result<_>>()?;
+        }
+        state = params.mds_rows()
+            .map(|row| row.iter().zip(&state).fold(Num::constant(Fr::zero()), |sum, (m, v)| sum.add(&v.scale(*m))))
+            .collect();
+    }
+    Ok(state.swap_remove(0))
+}
+
+/// Root of a Merkle path, matching `SecretProtectionTree` in `circuits/tree.circom`.
+/// A path index of `1` puts the current hash on the right.
+pub fn merkle_root_gadget(cs: &ConstraintSystemRef<Fr>, leaf: Num, path_indices: &[Num], siblings: &[Num]) -> Result<Num> {
+    path_indices.iter().zip(siblings).try_fold(leaf, |hash, (index, sibling)| {
+        index.enforce_bit(cs)?;
+        let swap = index.mul(cs, &sibling.sub(&hash))?;
+        let left = hash.add(&swap);
+        let right = sibling.sub(&swap);
+        poseidon_gadget(cs, &[left, right])
+    })
+}
+
+/// Leaf and nullifier of a leaf, matching `HardenedLeaf(maxRounds)` in `circuits/leaf.circom`,
+/// or the legacy `Poseidon(question, answer)` leaf when `max_rounds` is zero
+fn leaf_gadget(
+    cs: &ConstraintSystemRef<Fr>,
+    question: Num,
+    answer: Num,
+    salt: Option<U256>,
+    rounds: Option<U256>,
+    max_rounds: u32,
+) -> Result<(Num, Num)> {
+    let key = if max_rounds == 0 {
+        answer
+    } else {
+        let salt = Num::witness(cs, salt)?;
+        let rounds = Num::witness(cs, rounds)?;
+        let mut keys = vec![answer];
+        for _ in 0..max_rounds {
+            let next = poseidon_gadget(cs, &[keys.last().unwrap().clone(), salt.clone()])?;
+            keys.push(next);
+        }
+
+        let mut key = Num::constant(Fr::zero());
+        let mut found = Num::constant(Fr::zero());
+        for (i, value) in keys.iter().enumerate() {
+            let is_round = rounds.is_equal(cs, &Num::constant(Fr::from(i as u64)))?;
+            key = key.add(&is_round.mul(cs, value)?);
+            found = found.add(&is_round);
+        }
+        found.enforce_equal(cs, &Num::constant(Fr::one()))?;
+        key
+    };
+    let leaf = poseidon_gadget(cs, &[question, key.clone()])?;
+    let nullifier = poseidon_gadget(cs, &[key])?;
+    Ok((leaf, nullifier))
+}
+
+fn path_witness(cs: &ConstraintSystemRef<Fr>, levels: usize, path: Option<&MerkleProof<PoseidonHash>>) -> Result<(Vec<Num>, Vec<Num>)> {
+    if let Some(path) = path {
+        assert_eq!(path.0.len(), levels, "Path length differs from the circuit levels");
+    }
+    let mut indices = Vec::with_capacity(levels);
+    let mut siblings = Vec::with_capacity(levels);
+    for i in 0..levels {
+        let branch = path.map(|v| match v.0[i] {
+            Branch::Left(sibling) => (U256::zero(), sibling),
+            Branch::Right(sibling) => (U256::one(), sibling),
+        });
+        indices.push(Num::witness(cs, branch.map(|v| v.0))?);
+        siblings.push(Num::witness(cs, branch.map(|v| v.1))?);
+    }
+    Ok((indices, siblings))
+}
+
+/// The `recover` relation of `circuits/recover.circom`, or of `recover_hardened.circom` when `max_rounds` is set.
+/// Public inputs are `[nullifier, root, question, new_owner]`.
+#[derive(Clone)]
+pub struct RecoverCircuit {
+    /// Tree depth minus one, `nLevels` of the circuit
+    pub levels: usize,
+    pub max_rounds: u32,
+    /// `None` when generating parameters
+    pub input: Option<RecoverInput>,
+}
+
+impl ConstraintSynthesizer<Fr> for RecoverCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<()> {
+        let input = self.input.as_ref();
+        let nullifier = Num::input(&cs, input.map(RecoverInput::nullifier))?;
+        let root = Num::input(&cs, input.map(|v| v.root))?;
+        let question = Num::input(&cs, input.map(|v| v.question))?;
+        Num::input(&cs, input.map(|v| v.new_owner))?;
+
+        let (indices, siblings) = path_witness(&cs, self.levels, input.map(|v| &v.path))?;
+        let answer = Num::witness(&cs, input.map(|v| v.answer))?;
+        let salt = input.map(|v| v.salt.unwrap_or_default());
+        let rounds = input.map(|v| v.rounds.unwrap_or_default());
+
+        let (leaf, computed_nullifier) = leaf_gadget(&cs, question, answer, salt, rounds, self.max_rounds)?;
+        merkle_root_gadget(&cs, leaf, &indices, &siblings)?.enforce_equal(&cs, &root)?;
+        computed_nullifier.enforce_equal(&cs, &nullifier)
+    }
+}
+
+/// The relation of `circuits/recover_aggregate.circom`, answers to `count` questions under one root.
+/// Public inputs are `[nullifier_0, .., nullifier_count-1, root, question_0, .., question_count-1, new_owner]`.
+/// The leaves need not differ here, the contract rejects repeated nullifiers and questions.
+#[derive(Clone)]
+pub struct RecoverAggregateCircuit {
+    /// Tree depth minus one, `nLevels` of the circuit
+    pub levels: usize,
+    /// Number of answers, `k` of the circuit
+    pub count: usize,
+    pub max_rounds: u32,
+    /// `None` when generating parameters
+    pub input: Option<RecoverAggregateInput>,
+}
+
+impl ConstraintSynthesizer<Fr> for RecoverAggregateCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<()> {
+        let input = self.input.as_ref();
+        let leaf = |i: usize| input.map(|v| v.leaves[i]);
+        let nullifiers = (0..self.count).map(|i| Num::input(&cs, leaf(i).and_then(|v| v.nullifier().ok()))).collect::<Result<Vec<_>>>()?;
+        let root = Num::input(&cs, input.map(|v| v.root))?;
+        let questions = (0..self.count).map(|i| Num::input(&cs, leaf(i).map(|v| v.question))).collect::<Result<Vec<_>>>()?;
+        Num::input(&cs, input.map(|v| v.new_owner))?;
+
+        for (i, (question, nullifier)) in questions.into_iter().zip(nullifiers).enumerate() {
+            let (indices, siblings) = path_witness(&cs, self.levels, input.map(|v| &v.paths[i]))?;
+            let answer = Num::witness(&cs, leaf(i).map(|v| v.answer))?;
+            let (leaf_hash, computed_nullifier) =
+                leaf_gadget(&cs, question, answer, leaf(i).map(|v| v.salt), leaf(i).map(|v| v.rounds), self.max_rounds)?;
+            merkle_root_gadget(&cs, leaf_hash, &indices, &siblings)?.enforce_equal(&cs, &root)?;
+            computed_nullifier.enforce_equal(&cs, &nullifier)?;
+        }
+        Ok(())
+    }
+}
+
+/// The `update` relation of `circuits/update.circom`, or of `update_hardened.circom` when `max_rounds` is set.
+/// Public inputs are `[leaf, root, oldQuestion, newQuestion]`.
+#[derive(Clone)]
+pub struct UpdateCircuit {
+    /// Tree depth minus one, `nLevels` of the circuit
+    pub levels: usize,
+    pub max_rounds: u32,
+    /// `None` when generating parameters
+    pub input: Option<UpdateInput>,
+}
+
+impl ConstraintSynthesizer<Fr> for UpdateCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<()> {
+        let input = self.input.as_ref();
+        let new_leaf = Num::input(&cs, input.map(UpdateInput::new_leaf))?;
+        let root = Num::input(&cs, input.map(|v| v.root))?;
+        let old_question = Num::input(&cs, input.map(|v| v.old_question))?;
+        let new_question = Num::input(&cs, input.map(|v| v.new_question))?;
+
+        let (indices, siblings) = path_witness(&cs, self.levels, input.map(|v| &v.path))?;
+        let old_answer = Num::witness(&cs, input.map(|v| v.old_answer))?;
+        let new_answer = Num::witness(&cs, input.map(|v| v.new_answer))?;
+
+        let (old_leaf, _) = leaf_gadget(
+            &cs,
+            old_question,
+            old_answer,
+            input.map(|v| v.old_salt.unwrap_or_default()),
+            input.map(|v| v.old_rounds.unwrap_or_default()),
+            self.max_rounds,
+        )?;
+        merkle_root_gadget(&cs, old_leaf, &indices, &siblings)?.enforce_equal(&cs, &root)?;
+
+        let (computed_leaf, _) = leaf_gadget(
+            &cs,
+            new_question,
+            new_answer,
+            input.map(|v| v.new_salt.unwrap_or_default()),
+            input.map(|v| v.new_rounds.unwrap_or_default()),
+            self.max_rounds,
+        )?;
+        computed_leaf.enforce_equal(&cs, &new_leaf)
+    }
+}
+
+/// Generates Groth16 parameters for a circuit. The randomness is toxic waste, anyone who keeps it can forge proofs.
+pub fn setup<C: ConstraintSynthesizer<Fr>, R: RngCore + CryptoRng>(circuit: C, rng: &mut R) -> Result<ProvingKey<Bn254>> {
+    ark_groth16::generate_random_parameters::<Bn254, _, _>(circuit, rng)
+}
+
+pub fn prove<C: ConstraintSynthesizer<Fr>, R: RngCore + CryptoRng>(
+    circuit: C,
+    proving_key: &ProvingKey<Bn254>,
+    rng: &mut R,
+) -> Result<Proof<Bn254>> {
+    ark_groth16::create_random_proof(circuit, proving_key, rng)
+}
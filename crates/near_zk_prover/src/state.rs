@@ -0,0 +1,38 @@
+use crate::*;
+use borsh::BorshDeserialize;
+use serde_json::Value;
+
+/// Storage key of the contract struct
+const STATE_KEY: &[u8] = b"STATE";
+
+/// Path sources read from a contract state dump
+pub trait StatePathSource: Sized {
+    /// Takes the output of the `view_state` RPC, `{"values": [{"key", "value"}]}` with base64 keys and values
+    fn from_state_json(json: &str) -> Result<Self, CircuitInputError>;
+}
+
+impl StatePathSource for PathSource {
+    fn from_state_json(json: &str) -> Result<Self, CircuitInputError> {
+        let contract = contract_from_state_json(json)?;
+        Ok(PathSource::Tree(Box::new(contract.tree), contract.questions))
+    }
+}
+
+/// Decodes the contract from a `view_state` RPC response, `{"values": [{"key", "value"}]}` with base64 keys and values
+pub fn contract_from_state_json(json: &str) -> Result<Contract, CircuitInputError> {
+    let json: Value = serde_json::from_str(json).map_err(|e| CircuitInputError(e.to_string()))?;
+    let values = json.get("result").unwrap_or(&json).get("values").and_then(Value::as_array);
+    for entry in values.into_iter().flatten() {
+        let key = base64_field(entry, "key")?;
+        if key == STATE_KEY {
+            let value = base64_field(entry, "value")?;
+            return Contract::try_from_slice(&value).map_err(|e| CircuitInputError(e.to_string()));
+        }
+    }
+    Err(CircuitInputError("No contract state in the state dump".to_string()))
+}
+
+fn base64_field(entry: &Value, name: &str) -> Result<Vec<u8>, CircuitInputError> {
+    let value = entry.get(name).and_then(Value::as_str).ok_or_else(|| CircuitInputError(format!("{}: not a string", name)))?;
+    base64::decode(value).map_err(|e| CircuitInputError(format!("{}: {}", name, e)))
+}
@@ -0,0 +1,229 @@
+use crate::*;
+
+use std::fmt::Display;
+use std::io::{Error, ErrorKind, Result};
+use std::path::Path;
+
+use ark_bn254::FrParameters;
+use ark_ff::{FpParameters, PrimeField};
+use serde_json::Value;
+use wasmi::core::Trap;
+use wasmi::{Caller, Engine, Extern, Instance, Linker, Memory, MemoryType, Module, Store, WasmParams, WasmResults};
+
+/// Circom witness generator ABI supported by `WitnessCalculator`
+const CIRCOM_VERSION: u32 = 2;
+
+/// Pages of the `env.memory` import of older circom 2 modules, as circom's `witness_calculator.js` creates it
+const IMPORTED_MEMORY_PAGES: u32 = 2000;
+
+fn invalid<T>(message: impl Into<String>) -> Result<T> {
+    Err(Error::new(ErrorKind::InvalidData, message.into()))
+}
+
+fn wasm_error(error: impl Display) -> Error {
+    Error::other(error.to_string())
+}
+
+/// 64 bit FNV-1a hash of a signal name, which circom uses to look up input signals
+pub fn signal_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Messages printed by the circuit, `log` calls and the message of a failed `assert`
+#[derive(Default)]
+struct Messages {
+    error: String,
+    log: Vec<String>,
+}
+
+/// Reads the message the module has buffered, one `getMessageChar` call per character
+fn read_message(caller: &mut Caller<'_, Messages>) -> std::result::Result<String, Trap> {
+    let get_char = caller
+        .get_export("getMessageChar")
+        .and_then(Extern::into_func)
+        .ok_or_else(|| Trap::new("Missing getMessageChar export"))?
+        .typed::<(), i32>(&caller)
+        .map_err(|e| Trap::new(e.to_string()))?;
+    let mut message = String::new();
+    loop {
+        match get_char.call(&mut *caller, ()).map_err(|e| Trap::new(e.to_string()))? {
+            0 => return Ok(message),
+            c => message.push(char::from_u32(c as u32).unwrap_or(char::REPLACEMENT_CHARACTER)),
+        }
+    }
+}
+
+fn exception_message(code: i32) -> &'static str {
+    match code {
+        1 => "Signal not found",
+        2 => "Too many signals set",
+        3 => "Signal already set",
+        4 => "Assert Failed",
+        5 => "Not enough memory",
+        6 => "Input signal array access exceeds the size",
+        _ => "Unknown error",
+    }
+}
+
+/// Runs the `<circuit>_js/<circuit>.wasm` witness generator circom emits, in place of `generate_witness.js`
+pub struct WitnessCalculator {
+    store: Store<Messages>,
+    instance: Instance,
+    n32: usize,
+    witness_size: usize,
+    /// Checks `assert`s and signal constraints while computing, like `generate_witness.js` with sanity checks on
+    pub sanity_check: bool,
+}
+
+impl WitnessCalculator {
+    pub fn new(wasm: &[u8]) -> Result<Self> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, wasm).map_err(wasm_error)?;
+        let mut store = Store::new(&engine, Messages::default());
+        let mut linker = Linker::<Messages>::new(&engine);
+        linker
+            .func_wrap("runtime", "exceptionHandler", |mut caller: Caller<'_, Messages>, code: i32| {
+                let detail = std::mem::take(&mut caller.data_mut().error);
+                Err::<(), _>(Trap::new(format!("{}{}", exception_message(code), detail)))
+            })
+            .map_err(wasm_error)?;
+        linker
+            .func_wrap("runtime", "printErrorMessage", |mut caller: Caller<'_, Messages>| {
+                let message = read_message(&mut caller)?;
+                caller.data_mut().error.push_str(&format!("\n{}", message));
+                Ok(())
+            })
+            .map_err(wasm_error)?;
+        linker
+            .func_wrap("runtime", "writeBufferMessage", |mut caller: Caller<'_, Messages>| {
+                let message = read_message(&mut caller)?;
+                caller.data_mut().log.push(message);
+                Ok(())
+            })
+            .map_err(wasm_error)?;
+        linker.func_wrap("runtime", "showSharedRWMemory", || {}).map_err(wasm_error)?;
+        if module.imports().any(|import| import.module() == "env" && import.name() == "memory") {
+            let ty = MemoryType::new(IMPORTED_MEMORY_PAGES, None).map_err(wasm_error)?;
+            let memory = Memory::new(&mut store, ty).map_err(wasm_error)?;
+            linker.define("env", "memory", memory).map_err(wasm_error)?;
+        }
+        let instance = linker.instantiate(&mut store, &module).and_then(|v| v.start(&mut store)).map_err(wasm_error)?;
+
+        let mut calculator = Self { store, instance, n32: 0, witness_size: 0, sanity_check: false };
+        let version: i32 = calculator.call("getVersion", ())?;
+        if version as u32 != CIRCOM_VERSION {
+            return invalid(format!("Unsupported circom version {}", version));
+        }
+        calculator.n32 = calculator.call::<(), i32>("getFieldNumLen32", ())? as usize;
+        calculator.call::<(), ()>("getRawPrime", ())?;
+        if calculator.read_shared()? != FrParameters::MODULUS {
+            return invalid("Only the BN254 scalar field is supported");
+        }
+        calculator.witness_size = calculator.call::<(), i32>("getWitnessSize", ())? as usize;
+        Ok(calculator)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(&std::fs::read(path)?)
+    }
+
+    pub fn witness_size(&self) -> usize {
+        self.witness_size
+    }
+
+    /// Lines printed by `log` in the circuit so far
+    pub fn logs(&self) -> &[String] {
+        &self.store.data().log
+    }
+
+    /// Computes the witness of named inputs: a JSON object like `circuits/recover_input.json`,
+    /// or any value serializing to one such as `RecoverInput`. Values are decimal strings or
+    /// numbers, negative ones taken modulo the field, and arrays of them for array signals.
+    pub fn calculate<T: Serialize>(&mut self, input: &T) -> Result<Vec<Fr>> {
+        let input = serde_json::to_value(input).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+        let signals = match input {
+            Value::Object(signals) => signals,
+            _ => return invalid("Input is not an object"),
+        };
+
+        self.call::<i32, ()>("init", self.sanity_check as i32)?;
+        let mut input_counter = 0;
+        for (name, value) in &signals {
+            let mut values = Vec::new();
+            flatten(value, &mut values).map_err(|e| Error::new(e.kind(), format!("{}: {}", name, e)))?;
+            let hash = signal_hash(name);
+            let (msb, lsb) = ((hash >> 32) as i32, hash as i32);
+            let size: i32 = self.call("getInputSignalSize", (msb, lsb))?;
+            if size < 0 {
+                return invalid(format!("Signal {} not found", name));
+            }
+            if values.len() != size as usize {
+                return invalid(format!("Signal {} takes {} values, got {}", name, size, values.len()));
+            }
+            for (i, value) in values.into_iter().enumerate() {
+                self.write_shared(value.into_repr())?;
+                self.call::<(i32, i32, i32), ()>("setInputSignal", (msb, lsb, i as i32))?;
+                input_counter += 1;
+            }
+        }
+        let input_size: i32 = self.call("getInputSize", ())?;
+        if input_counter < input_size as usize {
+            return invalid(format!("Not all inputs have been set. Only {} out of {}", input_counter, input_size));
+        }
+
+        (0..self.witness_size)
+            .map(|i| {
+                self.call::<i32, ()>("getWitness", i as i32)?;
+                Fr::from_repr(self.read_shared()?).map_or_else(|| invalid("Witness value out of range"), Ok)
+            })
+            .collect()
+    }
+
+    fn call<P: WasmParams, R: WasmResults>(&mut self, name: &str, params: P) -> Result<R> {
+        let func = self.instance.get_typed_func::<P, R>(&self.store, name).map_err(|e| wasm_error(format!("{}: {}", name, e)))?;
+        func.call(&mut self.store, params).map_err(wasm_error)
+    }
+
+    /// Field element in the shared memory, as little endian 32 bit words
+    fn read_shared(&mut self) -> Result<BigInteger256> {
+        let mut value = BigInteger256::default();
+        for j in 0..self.n32 {
+            let word = self.call::<i32, i32>("readSharedRWMemory", j as i32)? as u32 as u64;
+            match value.0.get_mut(j / 2) {
+                Some(limb) => *limb |= word << (32 * (j % 2)),
+                None if word == 0 => {}
+                None => return invalid("Field element wider than 256 bits"),
+            }
+        }
+        Ok(value)
+    }
+
+    fn write_shared(&mut self, value: BigInteger256) -> Result<()> {
+        for j in 0..self.n32 {
+            let word = value.0.get(j / 2).map_or(0, |limb| (limb >> (32 * (j % 2))) as u32);
+            self.call::<(i32, i32), ()>("writeSharedRWMemory", (j as i32, word as i32))?;
+        }
+        Ok(())
+    }
+}
+
+fn flatten(value: &Value, out: &mut Vec<Fr>) -> Result<()> {
+    match value {
+        Value::Array(values) => values.iter().try_for_each(|v| flatten(v, out)),
+        Value::String(value) => parse_signal(value).map(|v| out.push(v)),
+        Value::Number(value) => parse_signal(&value.to_string()).map(|v| out.push(v)),
+        _ => invalid("Signal values are numbers, strings or arrays"),
+    }
+}
+
+fn parse_signal(value: &str) -> Result<Fr> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let value = U256::from_dec_str(digits).map_or_else(|_| invalid(format!("Invalid number {}", value)), Ok)?;
+    let mut bytes = [0u8; 32];
+    value.to_little_endian(&mut bytes);
+    let value = Fr::from_le_bytes_mod_order(&bytes);
+    Ok(if negative { -value } else { value })
+}
@@ -0,0 +1,38 @@
+//! Artifacts of `circuits/fixture.circom` as circom and snarkjs write them, copied to `tests/data` by `make fixtures`
+
+use ark_bn254::Fr;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use near_zk_core::*;
+use near_zk_prover::*;
+
+fn read(name: &str) -> Vec<u8> {
+    std::fs::read(format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name)).expect("Run `make fixtures` first")
+}
+
+fn read_json(name: &str) -> serde_json::Value {
+    serde_json::from_slice(&read(name)).unwrap()
+}
+
+#[test]
+#[ignore = "needs `make fixtures`"]
+fn test_fixture_artifacts() {
+    let r1cs = R1csFile::parse(&read("fixture.r1cs")).unwrap();
+    assert_eq!((r1cs.n_wires, r1cs.n_public()), (5, 2));
+    let witness = parse_witness(&read("fixture_witness.wtns")).unwrap();
+    assert_eq!(witness[..3], [Fr::from(1u64), Fr::from(17u64), Fr::from(3u64)]);
+    assert!(r1cs.is_satisfied(&witness));
+    let mut calculator = WitnessCalculator::new(&read("fixture.wasm")).unwrap();
+    assert_eq!(calculator.calculate(&read_json("fixture_input.json")).unwrap(), witness);
+    let public = public_signals(&witness, r1cs.n_public()).iter().map(U256::to_string).collect::<Vec<_>>();
+    assert_eq!(serde_json::to_value(&public).unwrap(), read_json("fixture_public.json"));
+
+    // snarkjs exports the same key, and accepts our proof as we accept its
+    let zkey = Zkey::parse(&read("fixture_0000.zkey")).unwrap();
+    let vk = String::from_utf8(read("fixture_verification_key.json")).unwrap();
+    assert_eq!(snarkjs_json(&CircomVerificationKey::from(zkey.verifying_key().clone())), vk);
+    let public = serde_json::to_string(&public).unwrap();
+    let proof = zkey.prove(&witness, &mut StdRng::seed_from_u64(0)).unwrap();
+    assert_eq!(verify_circom_proof(&vk, &snarkjs_json(&CircomProof::from(proof)), &public), Ok(true));
+    let snarkjs_proof = String::from_utf8(read("fixture_proof.json")).unwrap();
+    assert_eq!(verify_circom_proof(&vk, &snarkjs_proof, &public), Ok(true));
+}
@@ -0,0 +1,157 @@
+use ark_bn254::Fr;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use near_zk_prover::*;
+use serde_json::json;
+
+/// Module with the ABI of circom's witness generator for `c <== a * b[0] + b[1]`,
+/// witness `[1, c, a, b[0], b[1]]`, computed on the low 64 bits only.
+/// It logs when `a` is set and fails an assert when `a` is zero.
+const MOCK_WAT: &str = r#"
+(module
+  (import "runtime" "exceptionHandler" (func $exception (param i32)))
+  (import "runtime" "printErrorMessage" (func $print_error))
+  (import "runtime" "writeBufferMessage" (func $write_buffer))
+  (import "runtime" "showSharedRWMemory" (func $show_memory))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "a set\00")
+  (data (i32.const 16) "a is zero\00")
+  (global $message (mut i32) (i32.const 0))
+  ;; shared memory at 256, witness slots of 8 bytes at 512
+  (func (export "getVersion") (result i32) (i32.const 2))
+  (func (export "getFieldNumLen32") (result i32) (i32.const 8))
+  (func (export "getWitnessSize") (result i32) (i32.const 5))
+  (func (export "getInputSize") (result i32) (i32.const 3))
+  (func (export "init") (param i32)
+    (i64.store (i32.const 512) (i64.const 1)))
+  (func (export "getRawPrime")
+    (i32.store (i32.const 256) (i32.const -268435455))
+    (i32.store (i32.const 260) (i32.const 1138881939))
+    (i32.store (i32.const 264) (i32.const 2042196113))
+    (i32.store (i32.const 268) (i32.const 674490440))
+    (i32.store (i32.const 272) (i32.const -2122229667))
+    (i32.store (i32.const 276) (i32.const -1202698826))
+    (i32.store (i32.const 280) (i32.const -516841431))
+    (i32.store (i32.const 284) (i32.const 811880050)))
+  (func (export "readSharedRWMemory") (param $i i32) (result i32)
+    (i32.load (i32.add (i32.const 256) (i32.shl (local.get $i) (i32.const 2)))))
+  (func (export "writeSharedRWMemory") (param $i i32) (param $v i32)
+    (i32.store (i32.add (i32.const 256) (i32.shl (local.get $i) (i32.const 2))) (local.get $v)))
+  (func (export "getMessageChar") (result i32)
+    (local $c i32)
+    (local.set $c (i32.load8_u (global.get $message)))
+    (if (local.get $c) (then (global.set $message (i32.add (global.get $message) (i32.const 1)))))
+    (local.get $c))
+  ;; slot of the input signal, -1 when unknown
+  (func $slot (param $msb i32) (param $lsb i32) (result i32)
+    (if (i32.and (i32.eq (local.get $msb) (i32.const -1352410036)) (i32.eq (local.get $lsb) (i32.const -2046694260)))
+      (then (return (i32.const 2))))
+    (if (i32.and (i32.eq (local.get $msb) (i32.const -1352409268)) (i32.eq (local.get $lsb) (i32.const -2046692955)))
+      (then (return (i32.const 3))))
+    (i32.const -1))
+  (func (export "getInputSignalSize") (param $msb i32) (param $lsb i32) (result i32)
+    (local $slot i32)
+    (local.set $slot (call $slot (local.get $msb) (local.get $lsb)))
+    (if (i32.eq (local.get $slot) (i32.const 2)) (then (return (i32.const 1))))
+    (if (i32.eq (local.get $slot) (i32.const 3)) (then (return (i32.const 2))))
+    (i32.const -1))
+  (func (export "setInputSignal") (param $msb i32) (param $lsb i32) (param $i i32)
+    (local $slot i32)
+    (local.set $slot (call $slot (local.get $msb) (local.get $lsb)))
+    (if (i32.lt_s (local.get $slot) (i32.const 0)) (then (call $exception (i32.const 1)) (unreachable)))
+    (if (i32.eq (local.get $slot) (i32.const 2))
+      (then (global.set $message (i32.const 0)) (call $write_buffer)))
+    (i64.store
+      (i32.shl (i32.add (i32.add (local.get $slot) (local.get $i)) (i32.const 64)) (i32.const 3))
+      (i64.load (i32.const 256))))
+  (func (export "getWitness") (param $i i32)
+    (local $v i64)
+    (if (i32.eq (local.get $i) (i32.const 1))
+      (then
+        (if (i64.eqz (i64.load (i32.const 528)))
+          (then (global.set $message (i32.const 16)) (call $print_error) (call $exception (i32.const 4)) (unreachable)))
+        (local.set $v (i64.add (i64.mul (i64.load (i32.const 528)) (i64.load (i32.const 536))) (i64.load (i32.const 544)))))
+      (else (local.set $v (i64.load (i32.shl (i32.add (local.get $i) (i32.const 64)) (i32.const 3))))))
+    (i64.store (i32.const 256) (local.get $v))
+    (i64.store (i32.const 264) (i64.const 0))
+    (i64.store (i32.const 272) (i64.const 0))
+    (i64.store (i32.const 280) (i64.const 0))))
+"#;
+
+fn mock_calculator() -> WitnessCalculator {
+    WitnessCalculator::new(&wat::parse_str(MOCK_WAT).unwrap()).unwrap()
+}
+
+#[test]
+fn test_signal_hash() {
+    // FNV-1a test vectors
+    assert_eq!(signal_hash(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(signal_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(signal_hash("foobar"), 0x85944171f73967e8);
+}
+
+#[test]
+fn test_calculate_witness() {
+    let mut calculator = mock_calculator();
+    assert_eq!(calculator.witness_size(), 5);
+
+    let witness = calculator.calculate(&json!({"a": "3", "b": [5, "7"]})).unwrap();
+    assert_eq!(witness, [1u64, 22, 3, 5, 7].map(Fr::from));
+    assert_eq!(calculator.logs(), ["a set"]);
+
+    // Negative values wrap around the field, values above it are reduced
+    let p_minus_1 = "21888242871839275222246405745257275088548364400416034343698204186575808495616";
+    let p_plus_1 = "21888242871839275222246405745257275088548364400416034343698204186575808495618";
+    let witness = calculator.calculate(&json!({"a": "2", "b": ["-1", p_plus_1]})).unwrap();
+    assert_eq!(witness, calculator.calculate(&json!({"a": "2", "b": [p_minus_1, "1"]})).unwrap());
+    assert_eq!(witness[4], Fr::from(1u64));
+
+    // Inputs from a file
+    let input: serde_json::Value = serde_json::from_str(r#"{"b": ["1", "2"], "a": "4"}"#).unwrap();
+    assert_eq!(calculator.calculate(&input).unwrap()[1], Fr::from(6u64));
+}
+
+#[test]
+fn test_calculate_witness_errors() {
+    let mut calculator = mock_calculator();
+    let error = |input: serde_json::Value, calculator: &mut WitnessCalculator| calculator.calculate(&input).unwrap_err().to_string();
+
+    assert!(error(json!({"a": "3", "b": [5, 7], "d": "1"}), &mut calculator).contains("Signal d not found"));
+    assert!(error(json!({"a": "3", "b": [5]}), &mut calculator).contains("Signal b takes 2 values, got 1"));
+    assert!(error(json!({"b": [5, 7]}), &mut calculator).contains("Not all inputs have been set"));
+    assert!(error(json!({"a": "x", "b": [5, 7]}), &mut calculator).contains("Invalid number x"));
+    assert!(error(json!({"a": true, "b": [5, 7]}), &mut calculator).contains("a: "));
+    assert!(error(json!(["3"]), &mut calculator).contains("Input is not an object"));
+
+    let message = error(json!({"a": "0", "b": [5, 7]}), &mut calculator);
+    assert!(message.contains("Assert Failed") && message.contains("a is zero"), "{}", message);
+
+    // The calculator stays usable after a failed run
+    assert_eq!(calculator.calculate(&json!({"a": "1", "b": [1, 1]})).unwrap()[1], Fr::from(2u64));
+
+    assert!(WitnessCalculator::new(b"\0asm").is_err());
+    let other_field = MOCK_WAT.replace("(i32.const 811880050)", "(i32.const 811880051)");
+    assert!(WitnessCalculator::new(&wat::parse_str(other_field).unwrap()).is_err());
+}
+
+/// Computes witnesses with the generators `make circuit` leaves in `circuits/out`, then proves with the snarkjs key.
+/// Run by `make test`.
+#[test]
+#[ignore = "needs `make circuit`"]
+fn test_circom_wasm() {
+    let circuits = format!("{}/../../circuits", env!("CARGO_MANIFEST_DIR"));
+    for name in ["update", "recover"] {
+        let out = format!("{}/out", circuits);
+        let mut calculator = WitnessCalculator::from_file(format!("{0}/{1}_js/{1}.wasm", out, name)).expect("Run `make circuit` first");
+        let zkey = std::fs::read(format!("{}/{}_0000.zkey", out, name)).expect("Run `make circuit` first");
+        let input: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(format!("{}/{}_input.json", circuits, name)).unwrap()).unwrap();
+        let witness = calculator.calculate(&input).unwrap();
+        let wtns = std::fs::read(format!("{}/{}_witness.wtns", out, name)).unwrap();
+        assert_eq!(witness, parse_witness(&wtns).unwrap());
+
+        let zkey = Zkey::parse(&zkey).unwrap();
+        let proof = zkey.prove(&witness, &mut StdRng::seed_from_u64(0)).unwrap();
+        let verifying_key = ark_groth16::prepare_verifying_key(zkey.verifying_key());
+        assert!(ark_groth16::verify_proof(&verifying_key, &proof, &witness[1..=zkey.n_public]).unwrap());
+    }
+}
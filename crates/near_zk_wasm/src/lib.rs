@@ -0,0 +1,161 @@
+//! Browser bindings of `near_zk_core`, so wallets compute question and answer encodings, leaves,
+//! nullifiers, Merkle paths and circuit inputs with the contract's own code instead of a JavaScript port.
+//!
+//! `wasm-pack build crates/near_zk_wasm` writes the package with its TypeScript definitions to `pkg/`.
+
+use near_zk_core::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &str = r#"
+/** Question and answer text of one leaf. Hardened leaves set `salt` and `rounds`. */
+export interface Answer {
+    question: string;
+    answer: string;
+    /** Decimal salt of a hardened leaf, kept by the user */
+    salt?: string;
+    /** Hardening rounds of a hardened leaf, at most 16 */
+    rounds?: number;
+    /** `raw`, `standard` or `loose`, the profile stored with the question. Defaults to `raw`. */
+    normalization?: string;
+}
+
+/** Encoded leaf inputs, the leaf the contract stores and the nullifier a recover proof reveals */
+export interface Leaf {
+    question: string;
+    answer: string;
+    salt: string;
+    rounds: string;
+    leaf: string;
+    nullifier: string;
+}
+
+/** Result of the contract's `get_proof_path` view, `[root, pathIndices, siblings]` */
+export type ProofPath = [string, string[], string[]];
+
+/** Named circuit signals as snarkjs takes them, decimal strings and arrays of them */
+export type CircuitInput = Record<string, string | string[]>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Answer")]
+    pub type JsAnswer;
+
+    #[wasm_bindgen(typescript_type = "Leaf")]
+    pub type JsLeaf;
+
+    #[wasm_bindgen(typescript_type = "ProofPath")]
+    pub type JsProofPath;
+
+    #[wasm_bindgen(typescript_type = "CircuitInput")]
+    pub type JsCircuitInput;
+
+    #[wasm_bindgen(typescript_type = "string[]")]
+    pub type JsLeaves;
+}
+
+#[derive(Deserialize)]
+struct Answer {
+    question: String,
+    answer: String,
+    salt: Option<U256>,
+    rounds: Option<u32>,
+    normalization: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Leaf {
+    #[serde(flatten)]
+    witness: LeafWitness,
+    leaf: U256,
+    nullifier: U256,
+}
+
+fn js_error(error: impl ToString) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(js_error)
+}
+
+/// Plain objects and arrays, as `JSON.parse` would give, rather than `Map`s
+fn to_js<T: Serialize, R: JsCast>(value: &T) -> Result<R, JsError> {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    value.serialize(&serializer).map(JsCast::unchecked_into).map_err(js_error)
+}
+
+fn parse_normalization(name: Option<String>) -> Result<Option<NormalizationProfile>, JsError> {
+    name.map(|name| name.parse()).transpose().map_err(js_error)
+}
+
+fn answer_input(answer: JsAnswer) -> Result<AnswerInput, JsError> {
+    let answer: Answer = from_js(answer.into())?;
+    Ok(AnswerInput {
+        salt: answer.salt,
+        scheme: answer.rounds.map(|rounds| LeafScheme::Hardened { rounds }),
+        normalization: parse_normalization(answer.normalization)?,
+        ..AnswerInput::new(&answer.question, &answer.answer)
+    })
+}
+
+fn path_source(path: JsProofPath) -> Result<PathSource, JsError> {
+    let (root, path_indices, siblings): (String, Vec<String>, Vec<String>) = from_js(path.into())?;
+    PathSource::from_proof_path(&root, &path_indices, &siblings).map_err(js_error)
+}
+
+/// Question value the contract stores and the circuits take, as a decimal string
+#[wasm_bindgen(js_name = encodeQuestion)]
+pub fn encode_question(text: &str) -> String {
+    QuestionCodec::encode(text).to_string()
+}
+
+/// Answer value of the leaf hash after normalization, as a decimal string
+#[wasm_bindgen(js_name = encodeAnswer)]
+pub fn encode_answer_text(answer: &str, normalization: Option<String>) -> Result<String, JsError> {
+    Ok(encode_answer(answer, parse_normalization(normalization)?.unwrap_or_default()).to_string())
+}
+
+/// Leaf for `add_security_question` and the nullifier of its recover proof
+#[wasm_bindgen(js_name = computeLeaf)]
+pub fn compute_leaf(answer: JsAnswer) -> Result<JsLeaf, JsError> {
+    let witness = answer_input(answer)?.witness(None).map_err(js_error)?;
+    let (leaf, nullifier) = (witness.leaf().map_err(js_error)?, witness.nullifier().map_err(js_error)?);
+    to_js(&Leaf { witness, leaf, nullifier })
+}
+
+/// Merkle path of a leaf in the contract's tree of `depth` layers holding `leaves` in question order,
+/// in the format of the `get_proof_path` view
+#[wasm_bindgen(js_name = proofPath)]
+pub fn proof_path(depth: usize, leaves: JsLeaves, index: usize) -> Result<JsProofPath, JsError> {
+    let leaves: Vec<U256> = from_js(leaves.into())?;
+    let mut tree = MerkleTree::<PoseidonHash>::new(depth, U256::zero());
+    if leaves.len() > tree.num_leaves() {
+        return Err(JsError::new("More leaves than the tree holds"));
+    }
+    tree.set_range(0, leaves);
+    let proof = tree.proof(index).ok_or_else(|| JsError::new("Leaf index out of range"))?;
+    let siblings = proof.0.iter().map(|branch| match branch {
+        Branch::Left(sibling) | Branch::Right(sibling) => sibling.to_string(),
+    });
+    let path_indices = proof.path_index().iter().map(U256::to_string).collect::<Vec<_>>();
+    to_js(&(tree.root().to_string(), path_indices, siblings.collect::<Vec<_>>()))
+}
+
+/// Input of the recover circuit, checked against the root of `path`
+#[wasm_bindgen(js_name = recoverInput)]
+pub fn recover_input(answer: JsAnswer, new_owner: &str, path: JsProofPath) -> Result<JsCircuitInput, JsError> {
+    let input = RecoverInput::new(&path_source(path)?, &answer_input(answer)?, new_owner).map_err(js_error)?;
+    to_js(&input)
+}
+
+/// Input of the update circuit replacing `old_answer` with `new_answer`, checked against the root of `path`
+#[wasm_bindgen(js_name = updateInput)]
+pub fn update_input(old_answer: JsAnswer, new_answer: JsAnswer, path: JsProofPath) -> Result<JsCircuitInput, JsError> {
+    let (old_answer, new_answer) = (answer_input(old_answer)?, answer_input(new_answer)?);
+    let input = UpdateInput::new(&path_source(path)?, &old_answer, &new_answer).map_err(js_error)?;
+    to_js(&input)
+}
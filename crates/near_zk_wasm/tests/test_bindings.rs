@@ -0,0 +1,91 @@
+//! Runs in Node with `wasm-pack test --node crates/near_zk_wasm`
+#![cfg(target_arch = "wasm32")]
+
+use near_zk_core::{hash1, hash2, LeafScheme, QuestionCodec, U256};
+use near_zk_wasm::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+// Questions of test_scene.rs after the update of the first one
+const QUESTIONS: [(&str, &str); 3] = [
+    ("which season do you like", "autumn"),
+    ("where are you from", "china"),
+    ("What's your favorite number", "666"),
+];
+
+fn js<T: JsCast>(value: Value) -> T {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap().unchecked_into()
+}
+
+fn to_json(value: impl Into<JsValue>) -> Value {
+    serde_wasm_bindgen::from_value(value.into()).unwrap()
+}
+
+fn circuit_input(json: &str) -> (Value, JsProofPath) {
+    let input: Value = serde_json::from_str(json).unwrap();
+    let path = js(json!([input["root"], input["pathIndices"], input["siblings"]]));
+    (input, path)
+}
+
+fn leaf(question: &str, answer: &str) -> U256 {
+    hash2(QuestionCodec::encode(question), QuestionCodec::encode(answer))
+}
+
+#[wasm_bindgen_test]
+fn test_encode() {
+    let (input, _) = circuit_input(include_str!("../../../circuits/recover_input.json"));
+    assert_eq!(encode_question("where are you from"), input["question"]);
+    assert_eq!(encode_answer_text("china", None).unwrap(), input["answer"]);
+    assert_eq!(encode_answer_text("  China ", Some("standard".to_string())).unwrap(), input["answer"]);
+    assert!(encode_answer_text("china", Some("other".to_string())).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_compute_leaf() {
+    let output = to_json(compute_leaf(js(json!({"question": "where are you from", "answer": "china"}))).unwrap());
+    assert_eq!(output["leaf"], leaf("where are you from", "china").to_string());
+    assert_eq!(output["nullifier"], hash1(QuestionCodec::encode("china")).to_string());
+    assert_eq!((&output["salt"], &output["rounds"]), (&json!("0"), &json!("0")));
+
+    let salt = "319187461284017342158731264781237461982374619827346198234";
+    let answer = json!({"question": "where are you from", "answer": "china", "salt": salt, "rounds": 16});
+    let scheme = LeafScheme::Hardened { rounds: 16 };
+    let expected = scheme.leaf(QuestionCodec::encode("where are you from"), QuestionCodec::encode("china"), U256::from_dec_str(salt).unwrap());
+    assert_eq!(to_json(compute_leaf(js(answer)).unwrap())["leaf"], expected.to_string());
+
+    assert!(compute_leaf(js(json!({"question": "q", "answer": "a", "rounds": 16}))).is_err());
+    assert!(compute_leaf(js(json!({"question": "q", "answer": "a", "salt": "1", "rounds": 17}))).is_err());
+    assert!(compute_leaf(js(json!({"question": "q"}))).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_proof_path() {
+    let (input, path) = circuit_input(include_str!("../../../circuits/recover_input.json"));
+    let leaves = QUESTIONS.map(|(question, answer)| leaf(question, answer).to_string());
+    assert_eq!(to_json(proof_path(3, js(json!(leaves)), 1).unwrap()), to_json(path));
+    assert_eq!(to_json(proof_path(3, js(json!(leaves)), 0).unwrap())[0], input["root"]);
+    assert!(proof_path(3, js(json!(leaves)), 4).is_err());
+    assert!(proof_path(2, js(json!(leaves)), 0).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_recover_input() {
+    let (input, path) = circuit_input(include_str!("../../../circuits/recover_input.json"));
+    let answer = || js(json!({"question": "where are you from", "answer": "china"}));
+    assert_eq!(to_json(recover_input(answer(), "bob.test.near", path.clone().unchecked_into()).unwrap()), input);
+
+    let wrong = js(json!({"question": "where are you from", "answer": "japan"}));
+    assert!(recover_input(wrong, "bob.test.near", path.clone().unchecked_into()).is_err());
+    assert!(recover_input(answer(), "Bob.test.near", path).is_err());
+    assert!(recover_input(answer(), "bob.test.near", js(json!(["1", ["2"], []]))).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_update_input() {
+    let (input, path) = circuit_input(include_str!("../../../circuits/update_input.json"));
+    let old = js(json!({"question": "What's your favorite food", "answer": "ice cream"}));
+    let new = js(json!({"question": "which season do you like", "answer": "autumn"}));
+    assert_eq!(to_json(update_input(old, new, path).unwrap()), input);
+}
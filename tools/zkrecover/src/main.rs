@@ -0,0 +1,223 @@
+//! Command line tool for operating a recovery contract, computing everything with the contract's own code.
+//!
+//! ```text
+//! zkrecover encode (--question TEXT | --answer TEXT [--normalization raw|standard|loose])
+//! zkrecover leaf --question TEXT --answer TEXT [--normalization PROFILE] [--salt N --rounds N]
+//! zkrecover inputs recover --question TEXT --answer TEXT --new-owner ACCOUNT (--state FILE | --proof-path FILE)
+//!     [--salt N] [--rounds N] [--normalization PROFILE] [--out FILE]
+//! zkrecover inputs update --question TEXT --answer TEXT --new-question TEXT --new-answer TEXT (--state FILE | --proof-path FILE)
+//!     [--salt N] [--rounds N] [--normalization PROFILE]
+//!     [--new-salt N] [--new-rounds N] [--new-normalization PROFILE] [--out FILE]
+//! zkrecover verify --verification-key FILE --proof FILE --public FILE
+//! zkrecover decode-state --state FILE
+//! zkrecover progress --state FILE
+//! ```
+//!
+//! `--state` takes the output of the `view_state` RPC, `--proof-path` the output of the `get_proof_path` view,
+//! and `verify` the snarkjs files the contract takes. Results are printed as JSON, `encode` prints a number.
+
+use std::collections::HashMap;
+use std::process::exit;
+
+use near_sdk::serde_json::{self, json};
+use near_sdk::AccountId;
+use near_zk_demo1::*;
+use near_zk_prover::*;
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match run(&args) {
+        Ok(output) => println!("{}", output),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            exit(1);
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let (command, args) = args.split_first().ok_or("missing command")?;
+    match command.as_str() {
+        "encode" => encode(&parse_options(args)?),
+        "leaf" => leaf(&parse_options(args)?),
+        "inputs" => {
+            let (kind, args) = args.split_first().ok_or("missing input kind, recover or update")?;
+            inputs(kind, &parse_options(args)?)
+        }
+        "verify" => verify(&parse_options(args)?),
+        "decode-state" => decode_state(&parse_options(args)?),
+        "progress" => progress(&parse_options(args)?),
+        _ => Err(format!("unknown command {}", command)),
+    }
+}
+
+fn encode(options: &HashMap<String, String>) -> Result<String, String> {
+    match (options.get("question"), options.get("answer")) {
+        (Some(question), None) => Ok(QuestionCodec::encode(question).to_string()),
+        (None, Some(answer)) => Ok(encode_answer(answer, normalization(options, "")?.unwrap_or_default()).to_string()),
+        _ => Err("pass one of --question or --answer".to_string()),
+    }
+}
+
+fn leaf(options: &HashMap<String, String>) -> Result<String, String> {
+    let witness = answer_input(options, "")?.witness(None).map_err(|e| e.to_string())?;
+    pretty(&json!({
+        "question": witness.question,
+        "answer": witness.answer,
+        "leaf": witness.leaf()?,
+        "nullifier": witness.nullifier()?,
+    }))
+}
+
+fn inputs(kind: &str, options: &HashMap<String, String>) -> Result<String, String> {
+    let source = match (options.get("state"), options.get("proof-path")) {
+        (Some(path), None) => PathSource::from_state_json(&read(path)?),
+        (None, Some(path)) => PathSource::from_proof_path_json(&read(path)?),
+        _ => return Err("pass one of --state or --proof-path".to_string()),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let json = match kind {
+        "recover" => {
+            let new_owner: AccountId = required(options, "new-owner")?.parse().map_err(|_| "invalid --new-owner")?;
+            let input = RecoverInput::new(&source, &answer_input(options, "")?, new_owner.as_str()).map_err(|e| e.to_string())?;
+            pretty(&input)?
+        }
+        "update" => {
+            let input = UpdateInput::new(&source, &answer_input(options, "")?, &answer_input(options, "new-")?)
+                .map_err(|e| e.to_string())?;
+            pretty(&input)?
+        }
+        _ => return Err(format!("unknown input kind {}", kind)),
+    };
+
+    match options.get("out") {
+        Some(path) => {
+            std::fs::write(path, json + "\n").map_err(|e| format!("{}: {}", path, e))?;
+            Ok(format!("wrote {}", path))
+        }
+        None => Ok(json),
+    }
+}
+
+fn verify(options: &HashMap<String, String>) -> Result<String, String> {
+    let verification_key = read(required(options, "verification-key")?)?;
+    let proof = read(required(options, "proof")?)?;
+    let public = read(required(options, "public")?)?;
+    match verify_circom_proof(&verification_key, &proof, &public)? {
+        true => Ok("valid".to_string()),
+        false => Err("invalid proof".to_string()),
+    }
+}
+
+fn decode_state(options: &HashMap<String, String>) -> Result<String, String> {
+    let contract = state(options)?;
+    let leaves = contract.tree.leaves();
+    let questions = contract
+        .questions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, question)| {
+            let question = question.as_ref()?;
+            Some(json!({
+                "index": index,
+                "question": question.value,
+                "text": question.readable_text(),
+                "scheme": question.scheme,
+                "normalization": question.normalization,
+                "weight": question.weight,
+                "leaf": leaves[index],
+            }))
+        })
+        .collect::<Vec<_>>();
+    pretty(&json!({
+        "owner_id": contract.owner_id,
+        "depth": contract.tree.depth(),
+        "tree_mode": contract.tree.mode(),
+        "root": contract.tree.root(),
+        "secondary_root": contract.get_secondary_root(),
+        "questions": questions,
+        "recovers": contract.recovers,
+        "recovered_weight": contract.recovered_weight,
+        "new_owner": contract.new_owner,
+        "replace_cooldown": contract.get_replace_cooldown(),
+        "last_replaced_at": contract.get_last_replaced_at(),
+        "queue_delay": contract.get_queue_delay(),
+        "queue": contract.queue,
+        "guardians": contract.guardians,
+        "recovery_policy": contract.recovery_policy,
+        "approvals": contract.approvals,
+    }))
+}
+
+/// Recovery completes once the recover proofs reach the policy's question weight and the approvals its guardian weight,
+/// then the contract resets. `required` and `remaining` are question weights, `approved_weight` counts the approvals of `new_owner`.
+fn progress(options: &HashMap<String, String>) -> Result<String, String> {
+    let contract = state(options)?;
+    let required = contract.required_answer_weight();
+    let recovered = contract.recovered_weight;
+    let threshold = contract.recovery_policy.guardian_threshold;
+    let approved = contract.new_owner.clone().map_or(0, |v| contract.get_approved_weight(v));
+    pretty(&json!({
+        "owner_id": contract.owner_id,
+        "new_owner": contract.new_owner,
+        "recovered": contract.recovers.len(),
+        "recovered_weight": recovered,
+        "required": required,
+        "remaining": required.saturating_sub(recovered),
+        "nullifiers": contract.recovers,
+        "approved_weight": approved,
+        "guardian_threshold": threshold,
+        "remaining_guardian_weight": threshold.saturating_sub(approved),
+        "approvals": contract.get_recovery_approvals(),
+    }))
+}
+
+fn state(options: &HashMap<String, String>) -> Result<Contract, String> {
+    contract_from_state_json(&read(required(options, "state")?)?).map_err(|e| e.to_string())
+}
+
+fn pretty<T: near_sdk::serde::Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+fn parse_options(args: &[String]) -> Result<HashMap<String, String>, String> {
+    let mut options = HashMap::new();
+    let mut args = args.iter();
+    while let Some(name) = args.next() {
+        let name = name.strip_prefix("--").ok_or_else(|| format!("unexpected argument {}", name))?;
+        let value = args.next().ok_or_else(|| format!("missing value of --{}", name))?;
+        options.insert(name.to_string(), value.clone());
+    }
+    Ok(options)
+}
+
+fn required<'a>(options: &'a HashMap<String, String>, name: &str) -> Result<&'a String, String> {
+    options.get(name).ok_or_else(|| format!("missing --{}", name))
+}
+
+fn read(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn normalization(options: &HashMap<String, String>, prefix: &str) -> Result<Option<NormalizationProfile>, String> {
+    options
+        .get(&format!("{}normalization", prefix))
+        .map(|profile| profile.parse().map_err(|_| format!("invalid --{}normalization", prefix)))
+        .transpose()
+}
+
+/// Answer options, `prefix` is `new-` for the new question of an update
+fn answer_input(options: &HashMap<String, String>, prefix: &str) -> Result<AnswerInput, String> {
+    let option = |name: &str| options.get(&format!("{}{}", prefix, name));
+    let mut input = AnswerInput::new(required(options, &format!("{}question", prefix))?, required(options, &format!("{}answer", prefix))?);
+    if let Some(salt) = option("salt") {
+        input.salt = Some(U256::from_dec_str(salt).map_err(|_| format!("invalid --{}salt", prefix))?);
+    }
+    if let Some(rounds) = option("rounds") {
+        let rounds = rounds.parse().map_err(|_| format!("invalid --{}rounds", prefix))?;
+        input.scheme = Some(LeafScheme::Hardened { rounds });
+    }
+    input.normalization = normalization(options, prefix)?;
+    Ok(input)
+}
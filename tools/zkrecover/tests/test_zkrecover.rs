@@ -0,0 +1,176 @@
+use std::process::Command;
+
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::json_types::{Base64VecU8, U64};
+use near_sdk::serde_json::{self, json, Value};
+use near_zk_demo1::*;
+use near_zk_prover::*;
+
+// Questions of test_scene.rs after the update of the first one
+const QUESTIONS: [(&str, &str); 3] = [
+    ("which season do you like", "autumn"),
+    ("where are you from", "china"),
+    ("What's your favorite number", "666"),
+];
+
+/// Runs the tool, returning stdout on success and stderr on failure
+fn zkrecover(args: &[&str]) -> Result<String, String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkrecover")).args(args).output().unwrap();
+    let text = |bytes: Vec<u8>| String::from_utf8(bytes).unwrap().trim_end().to_string();
+    if output.status.success() {
+        Ok(text(output.stdout))
+    } else {
+        Err(text(output.stderr))
+    }
+}
+
+fn json_output(args: &[&str]) -> Value {
+    serde_json::from_str(&zkrecover(args).unwrap()).unwrap()
+}
+
+fn write_file(name: &str, content: &str) -> String {
+    let path = format!("{}/{}", env!("CARGO_TARGET_TMPDIR"), name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn leaf(question: &str, answer: &str) -> U256 {
+    hash2(QuestionCodec::encode(question), QuestionCodec::encode(answer))
+}
+
+fn sample_contract() -> Contract {
+    let mut contract = Contract::new(3, String::new(), String::new(), None, Some(U64(0)));
+    for (question, answer) in QUESTIONS {
+        let leaf = leaf(question, answer);
+        contract.add_security_question(QuestionCodec::encode(question).to_string(), leaf.to_string(), Some(question.to_string()), None, None, None);
+    }
+    contract
+}
+
+fn state_dump(name: &str, contract: &Contract) -> String {
+    let value = Base64VecU8::from(contract.try_to_vec().unwrap());
+    let json = json!({ "result": { "values": [{ "key": Base64VecU8::from(b"STATE".to_vec()), "value": value }] } });
+    write_file(name, &json.to_string())
+}
+
+#[test]
+fn test_encode_and_leaf() {
+    let question = QuestionCodec::encode("where are you from").to_string();
+    assert_eq!(zkrecover(&["encode", "--question", "where are you from"]).unwrap(), question);
+    assert_eq!(zkrecover(&["encode", "--answer", "china"]).unwrap(), QuestionCodec::encode("china").to_string());
+    assert_eq!(
+        zkrecover(&["encode", "--answer", "  China ", "--normalization", "standard"]).unwrap(),
+        QuestionCodec::encode("china").to_string()
+    );
+    assert!(zkrecover(&["encode", "--answer", "china", "--normalization", "other"]).unwrap_err().contains("invalid --normalization"));
+    assert!(zkrecover(&["encode"]).is_err());
+
+    let output = json_output(&["leaf", "--question", "where are you from", "--answer", "china"]);
+    assert_eq!(output["question"], question);
+    assert_eq!(output["leaf"], leaf("where are you from", "china").to_string());
+    assert_eq!(output["nullifier"], hash1(QuestionCodec::encode("china")).to_string());
+
+    let salt = "319187461284017342158731264781237461982374619827346198234";
+    let args = ["leaf", "--question", "where are you from", "--answer", "china", "--salt", salt, "--rounds", "16"];
+    let scheme = LeafScheme::Hardened { rounds: 16 };
+    let (answer, salt) = (QuestionCodec::encode("china"), U256::from_dec_str(salt).unwrap());
+    assert_eq!(json_output(&args)["leaf"], scheme.leaf(QuestionCodec::encode("where are you from"), answer, salt).to_string());
+    assert!(zkrecover(&["leaf", "--question", "q", "--answer", "a", "--rounds", "16"]).unwrap_err().contains("needs a salt"));
+    assert!(zkrecover(&["leaf", "--question", "q", "--answer", "a", "--salt", "1", "--rounds", "17"]).is_err());
+}
+
+#[test]
+fn test_inputs() {
+    let contract = sample_contract();
+    let state = state_dump("inputs_state.json", &contract);
+    let path = contract.get_proof_path(QuestionCodec::encode("where are you from").to_string()).unwrap();
+    let proof_path = write_file("inputs_proof_path.json", &serde_json::to_string(&path).unwrap());
+
+    let expected: Value =
+        serde_json::from_str(include_str!("../../../circuits/recover_input.json")).unwrap();
+    let args = ["--question", "where are you from", "--answer", "china", "--new-owner", "bob.test.near"];
+    for source in [["--state", state.as_str()], ["--proof-path", proof_path.as_str()]] {
+        let output = json_output(&[&["inputs", "recover"], &args[..], &source].concat());
+        assert_eq!(output, expected);
+    }
+
+    let out = format!("{}/inputs_recover.json", env!("CARGO_TARGET_TMPDIR"));
+    assert_eq!(zkrecover(&[&["inputs", "recover", "--state", &state, "--out", &out], &args[..]].concat()).unwrap(), format!("wrote {}", out));
+    assert_eq!(serde_json::from_str::<Value>(&std::fs::read_to_string(&out).unwrap()).unwrap(), expected);
+
+    let wrong = ["inputs", "recover", "--state", &state, "--question", "where are you from", "--answer", "japan", "--new-owner", "bob.test.near"];
+    assert!(zkrecover(&wrong).unwrap_err().contains("Answer does not match the stored leaf"));
+    assert!(zkrecover(&["inputs", "other", "--state", &state]).unwrap_err().contains("unknown input kind"));
+}
+
+#[test]
+fn test_verify() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let source = PathSource::from_state_json(&std::fs::read_to_string(state_dump("verify_state.json", &sample_contract())).unwrap()).unwrap();
+    let input = RecoverInput::new(&source, &AnswerInput::new("where are you from", "china"), "bob.test.near").unwrap();
+    let proving_key = setup(RecoverCircuit { levels: 2, max_rounds: 0, input: None }, &mut rng).unwrap();
+    let proof = prove(RecoverCircuit { levels: 2, max_rounds: 0, input: Some(input.clone()) }, &proving_key, &mut rng).unwrap();
+
+    let verification_key = write_file("verify_key.json", &snarkjs_json(&CircomVerificationKey::from(proving_key.vk)));
+    let proof = write_file("verify_proof.json", &snarkjs_json(&CircomProof::from(proof)));
+    let mut signals = input.public_signals().iter().map(U256::to_string).collect::<Vec<_>>();
+    let public = write_file("verify_public.json", &snarkjs_json(&signals));
+    let verify = |public: &str| zkrecover(&["verify", "--verification-key", &verification_key, "--proof", &proof, "--public", public]);
+    assert_eq!(verify(&public).unwrap(), "valid");
+
+    signals[3] = encode_account_id("carol.test.near").unwrap().to_string();
+    assert_eq!(verify(&write_file("verify_other_owner.json", &snarkjs_json(&signals))).unwrap_err(), "error: invalid proof");
+    assert!(verify(&write_file("verify_malformed.json", "[\"x\"]")).unwrap_err().contains("Invalid public input x"));
+
+    // The nullifier plus the field modulus is the same field element, and rejected
+    let modulus = U256::from_dec_str("21888242871839275222246405745257275088548364400416034343698204186575808495617").unwrap();
+    signals[3] = input.new_owner.to_string();
+    signals[0] = (input.nullifier() + modulus).to_string();
+    assert!(verify(&write_file("verify_beyond_field.json", &snarkjs_json(&signals))).unwrap_err().contains("Invalid public input"));
+}
+
+#[test]
+fn test_decode_state_and_progress() {
+    let mut contract = sample_contract();
+    contract.recovers.push("123".to_string());
+    contract.recovered_weight = 1;
+    contract.new_owner = Some("bob.test.near".parse().unwrap());
+    let state = state_dump("decode_state.json", &contract);
+
+    let output = json_output(&["decode-state", "--state", &state]);
+    assert_eq!(output["owner_id"], contract.get_owner().to_string());
+    assert_eq!(output["root"], contract.tree.root().to_string());
+    assert_eq!(output["tree_mode"], "Legacy");
+    assert_eq!(output["questions"].as_array().unwrap().len(), 3);
+    assert_eq!(output["questions"][1]["text"], "where are you from");
+    assert_eq!(output["questions"][1]["leaf"], leaf("where are you from", "china").to_string());
+    assert_eq!(output["questions"][1]["scheme"], "Legacy");
+    assert_eq!(output["questions"][1]["weight"], 1);
+    assert_eq!(output["recovers"], json!(["123"]));
+
+    let output = json_output(&["progress", "--state", &state]);
+    assert_eq!(output["new_owner"], "bob.test.near");
+    assert_eq!((output["recovered"].as_u64(), output["required"].as_u64(), output["remaining"].as_u64()), (Some(1), Some(3), Some(2)));
+    assert_eq!(output["recovered_weight"], 1);
+    assert_eq!((output["approved_weight"].as_u64(), output["guardian_threshold"].as_u64()), (Some(0), Some(0)));
+
+    // 2 answers and a guardian weight of 3
+    contract.set_guardian("carol.test.near".parse().unwrap(), Some(2));
+    contract.set_guardian("dave.test.near".parse().unwrap(), None);
+    contract.set_recovery_policy(Some(2), 3);
+    contract.approvals.push(Approval { guardian: "carol.test.near".parse().unwrap(), new_owner: "bob.test.near".parse().unwrap() });
+    let state = state_dump("decode_state_guardians.json", &contract);
+    let output = json_output(&["decode-state", "--state", &state]);
+    assert_eq!(output["guardians"][1], json!({"account_id": "dave.test.near", "weight": 1}));
+    assert_eq!(output["recovery_policy"], json!({"answer_threshold": 2, "guardian_threshold": 3}));
+    let output = json_output(&["progress", "--state", &state]);
+    assert_eq!((output["required"].as_u64(), output["remaining"].as_u64()), (Some(2), Some(1)));
+    assert_eq!((output["approved_weight"].as_u64(), output["remaining_guardian_weight"].as_u64()), (Some(2), Some(1)));
+    assert_eq!(output["approvals"], json!([{"guardian": "carol.test.near", "new_owner": "bob.test.near"}]));
+
+    let empty = write_file("decode_empty.json", r#"{"values": []}"#);
+    assert!(zkrecover(&["progress", "--state", &empty]).unwrap_err().contains("No contract state"));
+    assert!(zkrecover(&["progress"]).unwrap_err().contains("missing --state"));
+    assert!(zkrecover(&["other"]).unwrap_err().contains("unknown command"));
+}